@@ -1,4 +1,5 @@
 use clap::Parser;
+use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -8,6 +9,53 @@ pub struct Cli {
     #[arg(short, long, env = "FW_INTERFACE", default_value = "")]
     pub interface: String,
 
+    /// Capture --interface with AF_PACKET TPACKET_V3 rings and fanout workers instead of
+    /// libpcap (Linux only; for busy 10G SPAN ports)
+    #[arg(long, env = "FW_AFPACKET")]
+    pub afpacket: bool,
+
+    /// Worker threads sharing each interface in --afpacket mode
+    #[arg(long, env = "FW_FANOUT_WORKERS", default_value_t = 4)]
+    pub fanout_workers: usize,
+
+    /// Ring buffer size per --afpacket worker (MB)
+    #[arg(long, env = "FW_RING_MB", default_value_t = 64)]
+    pub ring_mb: usize,
+
+    /// Analyse pcap/pcapng files instead of live traffic (several files and globs allowed;
+    /// `-` for stdin or a named pipe to follow a remote tcpdump). Live sources are ignored,
+    /// so a sensor's FW_INTERFACE and friends do not get in the way.
+    #[arg(short, long, num_args = 1..)]
+    pub read: Vec<String>,
+
+    /// Receive TZSP-encapsulated frames on this UDP port (default 37008)
+    #[arg(long, env = "FW_TZSP_PORT", num_args = 0..=1, default_missing_value = "37008")]
+    pub tzsp: Option<u16>,
+
+    /// Receive sFlow v5 packet samples on this UDP port (default 6343)
+    #[arg(long, env = "FW_SFLOW_PORT", num_args = 0..=1, default_missing_value = "6343")]
+    pub sflow: Option<u16>,
+
+    /// Collect NetFlow v5/v9 and IPFIX records on this UDP port (default 2055)
+    #[arg(long, env = "FW_NETFLOW_PORT", num_args = 0..=1, default_missing_value = "2055")]
+    pub netflow: Option<u16>,
+
+    /// Save the frames behind new facts to rotating pcapng files in this directory
+    #[arg(long, env = "FW_EVIDENCE_DIR")]
+    pub evidence_dir: Option<PathBuf>,
+
+    /// Start a new evidence file once the current one reaches this size (MB)
+    #[arg(long, env = "FW_EVIDENCE_MAX_MB", default_value_t = 100)]
+    pub evidence_max_mb: u64,
+
+    /// Start a new evidence file after this many minutes
+    #[arg(long, env = "FW_EVIDENCE_MAX_MINUTES", default_value_t = 60)]
+    pub evidence_max_minutes: u64,
+
+    /// Delete the oldest evidence files once the directory grows past this size (MB)
+    #[arg(long, env = "FW_EVIDENCE_MAX_TOTAL_MB", default_value_t = 2048)]
+    pub evidence_max_total_mb: u64,
+
     /// Path to the SQLite database file
     #[arg(short, long, default_value = "/var/lib/field_watcher/assets.db", env = "FW_DB_PATH")]
     pub db_path: String,
@@ -16,6 +64,22 @@ pub struct Cli {
     #[arg(long)]
     pub list: bool,
 
+    /// List hosts learned from NetFlow/IPFIX records with their traffic volumes
+    #[arg(long)]
+    pub flows: bool,
+
+    /// Show the evidence frames behind what is known about a host (MAC or IP)
+    #[arg(long, value_name = "HOST")]
+    pub evidence_for: Option<String>,
+
+    /// Report devices syncing time from public or unapproved NTP servers
+    #[arg(long)]
+    pub ntp_report: bool,
+
+    /// Approved NTP server IPs for --ntp-report (comma separated)
+    #[arg(long, env = "FW_APPROVED_NTP", value_delimiter = ',')]
+    pub approved_ntp: Vec<String>,
+
     /// Remove all data from the databases
     #[arg(long)]
     pub reset: bool,
@@ -24,3 +88,25 @@ pub struct Cli {
     #[arg(long)]
     pub verbose: bool,
 }
+
+impl Cli {
+    /// Expands `--read` arguments in the order given; each glob is sorted, so rotated captures
+    /// (`dump-*.pcap`) are read oldest first. A pattern that matches nothing is an error.
+    pub fn capture_files(&self) -> Result<Vec<PathBuf>, String> {
+        let mut files = Vec::new();
+        for pattern in &self.read {
+            if pattern == "-" {
+                files.push(PathBuf::from(pattern));
+                continue;
+            }
+            let paths = glob::glob(pattern).map_err(|e| format!("invalid pattern {}: {}", pattern, e))?;
+            let mut matched: Vec<PathBuf> = paths.filter_map(|p| p.ok()).collect();
+            if matched.is_empty() {
+                return Err(format!("no capture files match {}", pattern));
+            }
+            matched.sort();
+            files.extend(matched);
+        }
+        Ok(files)
+    }
+}
@@ -1,14 +1,277 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+/// Confidence of a sighting from frames captured on the wire.
+pub const CONFIDENCE_WIRE: u8 = 100;
+/// Confidence of a sighting relayed in router flow records, where the MAC may be a previous hop.
+pub const CONFIDENCE_FLOW: u8 = 50;
+
+/// Format of the `booted_at` attribute (UTC, to the second).
+pub const BOOT_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Asset {
     pub ip_address: String,
     pub mac_address: String,
     pub hostname: Option<String>,
     pub vendor: Option<String>,
+    /// Hardware model/board as self-reported by the device (MNDP, Ubiquiti discovery).
+    pub model: Option<String>,
+    pub firmware: Option<String>,
+    /// Coarse operating system family, e.g. from SMB browser announcements.
+    pub os_guess: Option<String>,
     pub vlan_id: u16,
+    /// Overlay or mirror session the asset was seen through, e.g. `vxlan:5001` or `erspan:12`.
+    pub segment: Option<String>,
     pub discovery_method: String,
+    /// `CONFIDENCE_WIRE` or `CONFIDENCE_FLOW`; lower-confidence sightings never overwrite higher ones.
+    pub confidence: u8,
     pub first_seen_at: DateTime<Utc>,
     pub last_seen_at: DateTime<Utc>,
 }
+
+/// What a sniffer hands over to the sync loop for every matching packet.
+#[derive(Debug, Clone)]
+pub struct Discovery {
+    /// `None` when the capture carried no MAC to key an asset on (e.g. raw IP links).
+    pub asset: Option<Asset>,
+    /// Extra key/value facts about the asset (e.g. `role` = `gateway`).
+    pub attributes: Vec<(String, String)>,
+    pub observations: Vec<Observation>,
+    /// Capture timestamp of the packet (nanosecond precision); arrival time for TZSP, sFlow
+    /// and NetFlow, which carry none.
+    pub seen_at: DateTime<Utc>,
+    /// The matching frame, when evidence capture is enabled. The sync loop only saves it
+    /// when the packet told us something new.
+    pub evidence: Option<EvidenceFrame>,
+}
+
+/// A captured frame waiting to be written to an evidence file.
+#[derive(Debug, Clone)]
+pub struct EvidenceFrame {
+    /// pcap link type of `data`.
+    pub linktype: i32,
+    pub data: Vec<u8>,
+    /// Length on the wire, when the capture truncated the frame.
+    pub original_len: u32,
+}
+
+/// A frame in an evidence pcapng file: the file name and Wireshark's `frame.number`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvidenceRef {
+    pub file: String,
+    pub packet_number: u64,
+}
+
+/// Protocol-specific facts that live in their own tables next to `assets`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Observation {
+    Fhrp(FhrpHello),
+    Routing(RoutingHello),
+    Bacnet(BacnetDevice),
+    Iec61850(Iec61850Publisher),
+    Scada(ScadaLink),
+    Ptp(PtpClock),
+    Multicast(MulticastMembership),
+    MulticastQuerier(MulticastQuerier),
+    NtpServer(NtpServer),
+    NtpAssociation(NtpAssociation),
+    Mqtt(MqttSession),
+    WifiDevice(WifiDevice),
+    WifiProbe(WifiProbe),
+    FlowHost(FlowHost),
+}
+
+impl Observation {
+    /// Key the sync loop throttles on. Per-frame readings such as signal strength are left
+    /// out so that a beacon every 100 ms does not turn into a write every 100 ms.
+    pub fn throttle_key(&self) -> String {
+        match self {
+            Observation::WifiDevice(device) => format!("{:?}", WifiDevice { signal_dbm: None, ..device.clone() }),
+            Observation::WifiProbe(probe) => format!("{:?}", WifiProbe { signal_dbm: None, ..probe.clone() }),
+            other => format!("{:?}", other),
+        }
+    }
+
+    /// Observations that carry increments (flow counters) add up in the database, so every
+    /// one of them has to be written; the sync loop never throttles them.
+    pub fn is_increment(&self) -> bool {
+        matches!(self, Observation::FlowHost(_))
+    }
+}
+
+/// A single HSRP/VRRP/GLBP hello as seen from one group member.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FhrpHello {
+    pub protocol: String,
+    pub vlan_id: u16,
+    pub group_id: u16,
+    pub virtual_ip: Option<String>,
+    pub virtual_mac: Option<String>,
+    pub priority: u8,
+    pub state: String,
+    pub member_ip: String,
+    pub member_mac: String,
+}
+
+/// A routing protocol speaker and the neighbors it announced.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoutingHello {
+    pub protocol: String,
+    pub vlan_id: u16,
+    pub router_id: String,
+    pub area: Option<String>,
+    pub neighbors: Vec<String>,
+    pub speaker_ip: String,
+    pub speaker_mac: String,
+}
+
+/// A BACnet device announced via I-Am/I-Have, possibly relayed by a BBMD or router.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BacnetDevice {
+    pub device_instance: u32,
+    pub vlan_id: u16,
+    /// Original B/IP address, which differs from the frame source when a BBMD forwarded it.
+    pub ip_address: String,
+    /// Only known when the device itself sent the frame.
+    pub mac_address: Option<String>,
+    pub network: Option<u16>,
+    pub network_address: Option<String>,
+    pub forwarded_by: Option<String>,
+    pub vendor_id: Option<u32>,
+    pub max_apdu: Option<u32>,
+    pub segmentation: Option<String>,
+    pub object_name: Option<String>,
+}
+
+/// An IED publishing a GOOSE or Sampled Values stream.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Iec61850Publisher {
+    pub protocol: String,
+    pub vlan_id: u16,
+    pub appid: u16,
+    pub control_ref: String,
+    pub dataset: Option<String>,
+    pub conf_rev: u32,
+    pub publisher_mac: String,
+    pub destination_mac: String,
+}
+
+/// A DNP3 or IEC 60870-5-104 master/outstation pair with their protocol addresses.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScadaLink {
+    pub protocol: String,
+    pub vlan_id: u16,
+    pub master_ip: String,
+    pub master_mac: String,
+    pub master_address: Option<u16>,
+    pub outstation_ip: String,
+    pub outstation_mac: String,
+    pub outstation_address: u16,
+}
+
+/// A PTP master port as described by its Announce messages.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PtpClock {
+    pub vlan_id: u16,
+    pub domain: u8,
+    pub clock_identity: String,
+    pub grandmaster_identity: String,
+    pub priority1: u8,
+    pub priority2: u8,
+    pub clock_class: u8,
+    pub steps_removed: u16,
+    pub role: String,
+    pub ip_address: String,
+    pub mac_address: String,
+}
+
+/// An IGMP/MLD report or leave for one group from one listener.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MulticastMembership {
+    pub protocol: String,
+    pub vlan_id: u16,
+    pub group_address: String,
+    pub joined: bool,
+    pub member_ip: String,
+    pub member_mac: String,
+}
+
+/// The router or switch sending IGMP/MLD queries on a VLAN.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MulticastQuerier {
+    pub protocol: String,
+    pub vlan_id: u16,
+    pub querier_ip: String,
+    pub querier_mac: String,
+}
+
+/// An NTP server as described by its own replies or broadcasts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NtpServer {
+    pub vlan_id: u16,
+    pub server_ip: String,
+    pub version: u8,
+    pub stratum: u8,
+    pub reference_id: String,
+}
+
+/// A client polling a server. `client_mac` is the client's own MAC, seen on its segment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NtpAssociation {
+    pub vlan_id: u16,
+    pub client_ip: String,
+    pub client_mac: String,
+    pub server_ip: String,
+    /// The server is outside RFC 1918/ULA space, i.e. reached over the Internet.
+    pub server_public: bool,
+    pub version: u8,
+}
+
+/// An IoT client connecting to an MQTT broker.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MqttSession {
+    pub vlan_id: u16,
+    pub client_ip: String,
+    pub client_mac: String,
+    pub client_id: Option<String>,
+    pub username: Option<String>,
+    pub protocol_version: String,
+    pub broker_ip: String,
+    pub broker_port: u16,
+}
+
+/// An access point (from beacons/probe responses) or a client (from association requests).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WifiDevice {
+    pub mac_address: String,
+    /// `ap` or `client`.
+    pub role: String,
+    pub ssid: Option<String>,
+    /// The AP's BSSID; for clients, the AP they associate with.
+    pub bssid: String,
+    pub channel: Option<u8>,
+    pub security: Option<String>,
+    pub signal_dbm: Option<i8>,
+}
+
+/// A directed probe request: a client looking for a network it knows.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WifiProbe {
+    pub client_mac: String,
+    pub ssid: String,
+    pub signal_dbm: Option<i8>,
+    /// Locally administered MAC, as phones use while scanning; it changes between scans.
+    pub randomized: bool,
+}
+
+/// A source address seen in one exporter's NetFlow/IPFIX records, with traffic since the last report.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlowHost {
+    pub ip_address: String,
+    pub exporter: String,
+    pub mac_address: Option<String>,
+    pub vlan_id: Option<u16>,
+    pub bytes: u64,
+    pub packets: u64,
+}
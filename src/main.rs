@@ -6,15 +6,20 @@ mod storage;
 use cli::Cli;
 use clap::Parser;
 use storage::database::Database;
+use storage::evidence::EvidenceWriter;
 use network::sniffer::Sniffer;
+use pcap::Linktype;
 use std::{process, sync::Arc};
 use tokio::sync::mpsc;
-use tracing::{error, info, debug};
-use tracing_subscriber;
+use tokio::task::JoinSet;
+use tracing::{error, info, debug, warn};
 use chrono::{DateTime, Utc, Duration};
 use comfy_table::Table;
 use dashmap::DashMap;
 
+/// Last sync time, IP, hostname and method per MAC.
+type ThrottleCache = DashMap<String, (DateTime<Utc>, String, Option<String>, String)>;
+
 #[tokio::main]
 async fn main() {
     let args = Cli::parse();
@@ -59,10 +64,14 @@ async fn main() {
                     .set_content_arrangement(comfy_table::ContentArrangement::Dynamic)
                     .set_header(vec![
                         comfy_table::Cell::new("VLAN").add_attribute(comfy_table::Attribute::Bold),
+                        comfy_table::Cell::new("Segment").add_attribute(comfy_table::Attribute::Bold),
                         comfy_table::Cell::new("IP Address").add_attribute(comfy_table::Attribute::Bold),
                         comfy_table::Cell::new("MAC Address").add_attribute(comfy_table::Attribute::Bold),
                         comfy_table::Cell::new("Vendor").add_attribute(comfy_table::Attribute::Bold),
                         comfy_table::Cell::new("Hostname").add_attribute(comfy_table::Attribute::Bold),
+                        comfy_table::Cell::new("Model").add_attribute(comfy_table::Attribute::Bold),
+                        comfy_table::Cell::new("Firmware").add_attribute(comfy_table::Attribute::Bold),
+                        comfy_table::Cell::new("OS").add_attribute(comfy_table::Attribute::Bold),
                         comfy_table::Cell::new("Method").add_attribute(comfy_table::Attribute::Bold),
                         comfy_table::Cell::new("First Seen").add_attribute(comfy_table::Attribute::Bold),
                         comfy_table::Cell::new("Last Seen").add_attribute(comfy_table::Attribute::Bold),
@@ -83,10 +92,14 @@ async fn main() {
 
                     table.add_row(vec![
                         comfy_table::Cell::new(vlan_str).fg(comfy_table::Color::Cyan),
+                        comfy_table::Cell::new(asset.segment.clone().unwrap_or_else(|| "-".to_string())).fg(comfy_table::Color::Cyan),
                         comfy_table::Cell::new(asset.ip_address.clone()).fg(comfy_table::Color::Green),
                         comfy_table::Cell::new(asset.mac_address.clone()),
                         comfy_table::Cell::new(asset.vendor.clone().unwrap_or_else(|| "Unknown".to_string())),
                         comfy_table::Cell::new(asset.hostname.clone().unwrap_or_else(|| "-".to_string())).fg(comfy_table::Color::Yellow),
+                        comfy_table::Cell::new(asset.model.clone().unwrap_or_else(|| "-".to_string())),
+                        comfy_table::Cell::new(asset.firmware.clone().unwrap_or_else(|| "-".to_string())),
+                        comfy_table::Cell::new(asset.os_guess.clone().unwrap_or_else(|| "-".to_string())),
                         comfy_table::Cell::new(asset.discovery_method.clone()),
                         comfy_table::Cell::new(asset.first_seen_at.format("%Y-%m-%d %H:%M:%S").to_string()),
                         comfy_table::Cell::new(asset.last_seen_at.format("%Y-%m-%d %H:%M:%S").to_string()),
@@ -137,6 +150,127 @@ async fn main() {
         process::exit(0);
     }
 
+    if args.flows {
+        match db.get_flow_hosts() {
+            Ok(hosts) => {
+                let mut table = Table::new();
+                table.load_preset(comfy_table::presets::UTF8_FULL)
+                    .set_header(vec![
+                        comfy_table::Cell::new("IP Address").add_attribute(comfy_table::Attribute::Bold),
+                        comfy_table::Cell::new("Exporter").add_attribute(comfy_table::Attribute::Bold),
+                        comfy_table::Cell::new("MAC Address").add_attribute(comfy_table::Attribute::Bold),
+                        comfy_table::Cell::new("VLAN").add_attribute(comfy_table::Attribute::Bold),
+                        comfy_table::Cell::new("Bytes").add_attribute(comfy_table::Attribute::Bold),
+                        comfy_table::Cell::new("Packets").add_attribute(comfy_table::Attribute::Bold),
+                        comfy_table::Cell::new("First Seen").add_attribute(comfy_table::Attribute::Bold),
+                        comfy_table::Cell::new("Last Seen").add_attribute(comfy_table::Attribute::Bold),
+                    ]);
+
+                for (host, first_seen, last_seen) in hosts {
+                    table.add_row(vec![
+                        comfy_table::Cell::new(host.ip_address).fg(comfy_table::Color::Green),
+                        comfy_table::Cell::new(host.exporter),
+                        comfy_table::Cell::new(host.mac_address.unwrap_or_else(|| "-".to_string())),
+                        comfy_table::Cell::new(host.vlan_id.map(|v| v.to_string()).unwrap_or_else(|| "-".to_string())).fg(comfy_table::Color::Cyan),
+                        comfy_table::Cell::new(host.bytes.to_string()),
+                        comfy_table::Cell::new(host.packets.to_string()),
+                        comfy_table::Cell::new(first_seen.format("%Y-%m-%d %H:%M:%S").to_string()),
+                        comfy_table::Cell::new(last_seen.format("%Y-%m-%d %H:%M:%S").to_string()),
+                    ]);
+                }
+
+                if table.is_empty() {
+                    println!("No flow hosts found in database.");
+                } else {
+                    println!("{}", table);
+                }
+            },
+            Err(e) => error!("Failed to read database: {}", e),
+        }
+        process::exit(0);
+    }
+
+    if let Some(host) = &args.evidence_for {
+        match db.get_evidence(host) {
+            Ok(rows) => {
+                let mut table = Table::new();
+                table.load_preset(comfy_table::presets::UTF8_FULL)
+                    .set_content_arrangement(comfy_table::ContentArrangement::Dynamic)
+                    .set_header(vec![
+                        comfy_table::Cell::new("Fact").add_attribute(comfy_table::Attribute::Bold),
+                        comfy_table::Cell::new("File").add_attribute(comfy_table::Attribute::Bold),
+                        comfy_table::Cell::new("Frame").add_attribute(comfy_table::Attribute::Bold),
+                        comfy_table::Cell::new("Captured").add_attribute(comfy_table::Attribute::Bold),
+                    ]);
+
+                for (fact, evidence, captured_at) in rows {
+                    table.add_row(vec![
+                        comfy_table::Cell::new(fact).fg(comfy_table::Color::Yellow),
+                        comfy_table::Cell::new(evidence.file),
+                        comfy_table::Cell::new(evidence.packet_number.to_string()).fg(comfy_table::Color::Cyan),
+                        comfy_table::Cell::new(captured_at.format("%Y-%m-%d %H:%M:%S%.f").to_string()),
+                    ]);
+                }
+
+                if table.is_empty() {
+                    println!("No evidence recorded for {}.", host);
+                } else {
+                    println!("{}", table);
+                    println!("Open the file in Wireshark and filter on frame.number == <Frame>.");
+                }
+            },
+            Err(e) => error!("Failed to read database: {}", e),
+        }
+        process::exit(0);
+    }
+
+    if args.ntp_report {
+        match db.get_ntp_associations() {
+            Ok(associations) => {
+                let mut table = Table::new();
+                table.load_preset(comfy_table::presets::UTF8_FULL)
+                    .set_header(vec![
+                        comfy_table::Cell::new("VLAN").add_attribute(comfy_table::Attribute::Bold),
+                        comfy_table::Cell::new("Client IP").add_attribute(comfy_table::Attribute::Bold),
+                        comfy_table::Cell::new("Client MAC").add_attribute(comfy_table::Attribute::Bold),
+                        comfy_table::Cell::new("Hostname").add_attribute(comfy_table::Attribute::Bold),
+                        comfy_table::Cell::new("NTP Server").add_attribute(comfy_table::Attribute::Bold),
+                        comfy_table::Cell::new("Stratum").add_attribute(comfy_table::Attribute::Bold),
+                        comfy_table::Cell::new("Issue").add_attribute(comfy_table::Attribute::Bold),
+                    ]);
+
+                // Without an approved list, only Internet servers are flagged.
+                for (association, hostname, stratum) in associations {
+                    let approved = args.approved_ntp.iter().any(|ip| ip.trim() == association.server_ip);
+                    let issue = match (association.server_public, approved, args.approved_ntp.is_empty()) {
+                        (_, true, _) => continue,
+                        (true, false, _) => "public server",
+                        (false, false, false) => "unapproved server",
+                        (false, false, true) => continue,
+                    };
+
+                    table.add_row(vec![
+                        comfy_table::Cell::new(association.vlan_id.to_string()).fg(comfy_table::Color::Cyan),
+                        comfy_table::Cell::new(association.client_ip).fg(comfy_table::Color::Green),
+                        comfy_table::Cell::new(association.client_mac),
+                        comfy_table::Cell::new(hostname.unwrap_or_else(|| "-".to_string())).fg(comfy_table::Color::Yellow),
+                        comfy_table::Cell::new(association.server_ip),
+                        comfy_table::Cell::new(stratum.map(|s| s.to_string()).unwrap_or_else(|| "-".to_string())),
+                        comfy_table::Cell::new(issue).fg(comfy_table::Color::Red),
+                    ]);
+                }
+
+                if table.is_empty() {
+                    println!("No devices syncing to public or unapproved NTP servers.");
+                } else {
+                    println!("{}", table);
+                }
+            },
+            Err(e) => error!("Failed to read database: {}", e),
+        }
+        process::exit(0);
+    }
+
     if args.reset {
         if let Err(e) = db.reset_database() {
             error!("Failed to reset database: {}", e);
@@ -146,8 +280,18 @@ async fn main() {
         process::exit(0);
     }
 
-    if args.interface.is_empty() {
-        error!("Error: --interface is required.");
+    let offline = !args.read.is_empty();
+    if !offline && args.interface.is_empty() && args.tzsp.is_none() && args.sflow.is_none() && args.netflow.is_none() {
+        error!("Error: --interface, --tzsp, --sflow, --netflow or --read is required.");
+        process::exit(1);
+    }
+
+    if offline && (!args.interface.is_empty() || args.afpacket || args.tzsp.is_some() || args.sflow.is_some() || args.netflow.is_some()) {
+        info!("Reading captures; --interface, --afpacket, --tzsp, --sflow and --netflow are ignored.");
+    }
+
+    if !offline && args.afpacket && !cfg!(target_os = "linux") {
+        error!("Error: --afpacket is only available on Linux.");
         process::exit(1);
     }
 
@@ -155,64 +299,259 @@ async fn main() {
 
     let (tx, mut rx) = mpsc::channel(1000);
 
-    let interfaces: Vec<String> = args.interface.split_whitespace().map(|s| s.to_string()).collect();
-    for iface in interfaces {
-        let sniffer = Sniffer::new(iface);
-        let tx_clone = tx.clone();
-        tokio::task::spawn_blocking(move || {
-            sniffer.start(tx_clone);
+    let evidence = match &args.evidence_dir {
+        Some(dir) => {
+            let max_bytes = args.evidence_max_mb * 1024 * 1024;
+            let max_age = std::time::Duration::from_secs(args.evidence_max_minutes * 60);
+            let max_total_bytes = args.evidence_max_total_mb * 1024 * 1024;
+            match EvidenceWriter::new(dir.clone(), max_bytes, max_age, max_total_bytes) {
+                Ok(writer) => Some(Arc::new(writer)),
+                Err(e) => {
+                    error!("Failed to create evidence directory {}: {}", dir.display(), e);
+                    process::exit(1);
+                }
+            }
+        }
+        None => None,
+    };
+
+    // The evidence buffer is written out and the directory trimmed from here.
+    if let Some(writer) = evidence.clone() {
+        let db_clone = Arc::clone(&db);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(std::time::Duration::from_secs(1));
+            loop {
+                ticker.tick().await;
+                let writer = Arc::clone(&writer);
+                let db_clone = Arc::clone(&db_clone);
+                let _ = tokio::task::spawn_blocking(move || flush_evidence(&writer, &db_clone)).await;
+            }
         });
     }
 
-    let throttle_cache: Arc<DashMap<String, (DateTime<Utc>, String, Option<String>, String)>> = Arc::new(DashMap::new());
+    if offline {
+        let files = match args.capture_files() {
+            Ok(files) => files,
+            Err(e) => {
+                error!("Error: {}", e);
+                process::exit(1);
+            }
+        };
+        let (streams, files): (Vec<_>, Vec<_>) = files.into_iter().partition(|f| Sniffer::is_stream(f));
+        for stream in streams {
+            let name = if stream.as_os_str() == "-" { "stdin".to_string() } else { stream.display().to_string() };
+            let sniffer = Sniffer::new(name).with_evidence(evidence.is_some());
+            let tx_clone = tx.clone();
+            tokio::task::spawn_blocking(move || {
+                sniffer.read_stream(stream, tx_clone);
+            });
+        }
+
+        if !files.is_empty() {
+            let sniffer = Sniffer::new("read".to_string()).with_evidence(evidence.is_some());
+            let tx_clone = tx.clone();
+            tokio::task::spawn_blocking(move || {
+                sniffer.read_files(files, tx_clone);
+            });
+        }
+    } else {
+        let interfaces: Vec<String> = args.interface.split_whitespace().map(|s| s.to_string()).collect();
+        for iface in interfaces {
+            let sniffer = Sniffer::new(iface).with_evidence(evidence.is_some());
+            let tx_clone = tx.clone();
+            #[cfg(target_os = "linux")]
+            if args.afpacket {
+                let (workers, ring_mb) = (args.fanout_workers, args.ring_mb);
+                tokio::task::spawn_blocking(move || {
+                    sniffer.start_afpacket(workers, ring_mb, tx_clone);
+                });
+                continue;
+            }
+            tokio::task::spawn_blocking(move || {
+                sniffer.start(tx_clone);
+            });
+        }
+
+        if let Some(port) = args.tzsp {
+            let sniffer = Sniffer::new(format!("tzsp:{}", port)).with_evidence(evidence.is_some());
+            let tx_clone = tx.clone();
+            tokio::task::spawn_blocking(move || {
+                sniffer.listen_tzsp(port, tx_clone);
+            });
+        }
+
+        if let Some(port) = args.sflow {
+            let sniffer = Sniffer::new(format!("sflow:{}", port)).with_evidence(evidence.is_some());
+            let tx_clone = tx.clone();
+            tokio::task::spawn_blocking(move || {
+                sniffer.listen_sflow(port, tx_clone);
+            });
+        }
+
+        if let Some(port) = args.netflow {
+            let sniffer = Sniffer::new(format!("netflow:{}", port));
+            let tx_clone = tx.clone();
+            tokio::task::spawn_blocking(move || {
+                sniffer.listen_netflow(port, tx_clone);
+            });
+        }
+    }
+
+    // Live sources and named pipes never finish; in read mode the loop below ends once the
+    // files (and stdin) are exhausted.
+    drop(tx);
+
+    let throttle_cache: Arc<ThrottleCache> = Arc::new(DashMap::new());
+    // Attributes and observations are throttled on their full content, so changes go through immediately.
+    let fact_cache: Arc<DashMap<String, DateTime<Utc>>> = Arc::new(DashMap::new());
     let throttle_duration = Duration::seconds(10);
+    let mut fact_cache_pruned_at: Option<DateTime<Utc>> = None;
+
+    // DB writes run on the blocking pool; they are tracked so read mode can wait for them.
+    let mut writes = JoinSet::new();
 
     info!("Monitoring for hosts in real-time...");
 
-    while let Some(asset) = rx.recv().await {
-        let mac = asset.mac_address.clone();
-        let ip = asset.ip_address.clone();
-        let hostname = asset.hostname.clone();
-        let method = asset.discovery_method.clone();
-        let now = Utc::now();
-
-        let should_sync = if let Some(entry) = throttle_cache.get(&mac) {
-            let (last_sync, last_ip, last_hostname, last_method) = entry.value();
-            let ip_changed = last_ip != &ip;
-            let hostname_new = last_hostname.is_none() && hostname.is_some();
-            let method_changed = last_method != &method;
-            let time_passed = (now - *last_sync) > throttle_duration;
-            
-            ip_changed || hostname_new || method_changed || time_passed
-        } else {
-            true
-        };
+    while let Some(discovery) = rx.recv().await {
+        // Throttling follows packet time, so a capture file replays at the rate it was recorded.
+        let now = discovery.seen_at;
+        while writes.try_join_next().is_some() {}
+
+        // Expired facts would be written again anyway; drop them so the cache stays bounded.
+        if fact_cache_pruned_at.is_none_or(|t| (now - t) > Duration::minutes(1)) {
+            fact_cache.retain(|_, last| (now - *last) <= throttle_duration);
+            fact_cache_pruned_at = Some(now);
+        }
+        let asset_mac = discovery.asset.as_ref().map(|asset| asset.mac_address.clone());
+        // What this packet newly told us, to be tied to its evidence frame.
+        let mut facts: Vec<String> = Vec::new();
 
-        if should_sync {
-            // Re-check/preserve hostname from cache if current is None
-            let final_hostname = if hostname.is_none() {
-                throttle_cache.get(&mac).and_then(|e| e.value().2.clone())
+        if let Some(asset) = discovery.asset {
+            let mac = asset.mac_address.clone();
+            let ip = asset.ip_address.clone();
+            let hostname = asset.hostname.clone();
+            let method = asset.discovery_method.clone();
+
+            let should_sync = if let Some(entry) = throttle_cache.get(&mac) {
+                let (last_sync, last_ip, last_hostname, last_method) = entry.value();
+                let ip_changed = last_ip != &ip;
+                let hostname_new = last_hostname.is_none() && hostname.is_some();
+                let method_changed = last_method != &method;
+                let time_passed = (now - *last_sync) > throttle_duration;
+            
+                ip_changed || hostname_new || method_changed || time_passed
             } else {
-                hostname
+                true
             };
 
-            if args.verbose {
-                info!("Syncing: {} ({}) via {} VLAN: {} Hostname: {:?}", ip, mac, method, asset.vlan_id, final_hostname);
-            }
+            if should_sync {
+                // Re-check/preserve hostname from cache if current is None
+                let final_hostname = if hostname.is_none() {
+                    throttle_cache.get(&mac).and_then(|e| e.value().2.clone())
+                } else {
+                    hostname
+                };
 
-            throttle_cache.insert(mac, (now, ip, final_hostname.clone(), method));
+                if args.verbose {
+                    info!("Syncing: {} ({}) via {} VLAN: {} Hostname: {:?}", ip, mac, method, asset.vlan_id, final_hostname);
+                }
+
+                throttle_cache.insert(mac, (now, ip, final_hostname.clone(), method));
             
-            let mut sync_asset = asset.clone();
-            sync_asset.hostname = final_hostname;
+                let mut sync_asset = asset.clone();
+                sync_asset.hostname = final_hostname;
+
+                if discovery.evidence.is_some() {
+                    facts.push(format!("method={}", sync_asset.discovery_method));
+                    let named = [
+                        ("hostname", &sync_asset.hostname),
+                        ("model", &sync_asset.model),
+                        ("firmware", &sync_asset.firmware),
+                        ("os", &sync_asset.os_guess),
+                    ];
+                    facts.extend(named.iter().filter_map(|(k, v)| v.as_ref().map(|v| format!("{}={}", k, v))));
+                }
+
+                let db_clone = Arc::clone(&db);
+                writes.spawn_blocking(move || {
+                    if let Err(e) = db_clone.sync_asset(&sync_asset) {
+                        error!("DB Error: {}", e);
+                    }
+                });
+            } else if args.verbose {
+                debug!("Throttled: {} ({})", ip, mac);
+            }
+        }
 
+        let is_fresh = |key: String| -> bool {
+            match fact_cache.get(&key) {
+                Some(last) if (now - *last) <= throttle_duration => false,
+                _ => {
+                    fact_cache.insert(key, now);
+                    true
+                }
+            }
+        };
+
+        // Attributes hang off an asset; without one only the observations are kept.
+        let attributes: Vec<(String, String)> = match &asset_mac {
+            Some(mac) => discovery.attributes.into_iter()
+                .filter(|(k, v)| is_fresh(format!("{}|{}={}", mac, k, v)))
+                .collect(),
+            None => Vec::new(),
+        };
+        let observations: Vec<_> = discovery.observations.into_iter()
+            .filter(|o| o.is_increment() || is_fresh(o.throttle_key()))
+            .collect();
+
+        // Only frames that told us something new are saved.
+        if let Some(frame) = discovery.evidence
+            && let Some(writer) = &evidence {
+            facts.extend(attributes.iter().map(|(k, v)| format!("{}={}", k, v)));
+            facts.extend(observations.iter().map(|o| o.throttle_key()));
+            if !facts.is_empty() {
+                let mac = asset_mac.clone().unwrap_or_default();
+                let writer = Arc::clone(writer);
+                let db_clone = Arc::clone(&db);
+                writes.spawn_blocking(move || {
+                    let Some(saved) = writer.write(Linktype(frame.linktype), now, &frame.data, frame.original_len) else { return };
+                    if let Err(e) = db_clone.record_evidence(&mac, &facts, &saved, now) {
+                        error!("DB Error: {}", e);
+                    }
+                });
+            }
+        }
+
+        if !attributes.is_empty() || !observations.is_empty() {
             let db_clone = Arc::clone(&db);
-            tokio::task::spawn_blocking(move || {
-                if let Err(e) = db_clone.sync_asset(&sync_asset) {
+            writes.spawn_blocking(move || {
+                if let Some(mac) = &asset_mac
+                    && let Err(e) = db_clone.sync_attributes(mac, &attributes, now) {
                     error!("DB Error: {}", e);
                 }
+                for observation in &observations {
+                    match db_clone.record_observation(observation, now) {
+                        Ok(alerts) => alerts.iter().for_each(|alert| warn!("ALERT: {}", alert)),
+                        Err(e) => error!("DB Error: {}", e),
+                    }
+                }
             });
-        } else if args.verbose {
-            debug!("Throttled: {} ({})", ip, mac);
         }
     }
+    while writes.join_next().await.is_some() {}
+    if let Some(writer) = &evidence {
+        flush_evidence(writer, &db);
+    }
+    info!("All sources finished; database is up to date.");
+}
+
+/// Writes out buffered evidence, then deletes the oldest files past the size limit along with
+/// the evidence rows that point into them.
+fn flush_evidence(writer: &EvidenceWriter, db: &Database) {
+    writer.flush();
+    let pruned = writer.prune();
+    if !pruned.is_empty()
+        && let Err(e) = db.delete_evidence(&pruned) {
+        error!("DB Error: {}", e);
+    }
 }
@@ -0,0 +1,332 @@
+use crate::domain::models::Discovery;
+use crate::network::sniffer::Sniffer;
+use chrono::{DateTime, Utc};
+use pcap::{Capture, Linktype};
+use std::ffi::CString;
+use std::io;
+use std::mem;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::ptr;
+use std::sync::atomic::{fence, AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+
+/// Ring blocks are 1 MiB; `--ring-mb` is the number of blocks per worker.
+const BLOCK_SIZE: u32 = 1 << 20;
+const FRAME_SIZE: u32 = 2048;
+/// A block is handed over after this many ms even when not full, so quiet links stay live.
+const BLOCK_TIMEOUT_MS: u32 = 50;
+const STATS_INTERVAL: Duration = Duration::from_secs(60);
+
+const ARPHRD_ETHER: u32 = 1;
+const ARPHRD_IEEE80211_RADIOTAP: u32 = 803;
+const ARPHRD_NONE: u32 = 65534;
+
+impl Sniffer {
+    /// Captures with a TPACKET_V3 ring per worker thread, all joined to one PACKET_FANOUT group
+    /// so the kernel spreads the interface's traffic across them. Fanout hashes on the flow,
+    /// which keeps e.g. a DHCP exchange on one worker and in order. Kernel drop counters are
+    /// logged every minute.
+    pub fn start_afpacket(self, workers: usize, ring_mb: usize, tx: mpsc::Sender<Discovery>) {
+        let interface = self.interface.clone();
+
+        let linktype = match Self::afpacket_linktype(&interface) {
+            Ok(linktype) => linktype,
+            Err(e) => {
+                error!("AF_PACKET capture not possible on {}: {}", interface, e);
+                return;
+            }
+        };
+
+        // The same filter as the pcap backend, compiled by libpcap and attached in the kernel.
+        let program = match Capture::dead(linktype).and_then(|c| c.compile(&Self::filter_for(linktype), true)) {
+            Ok(program) => program,
+            Err(e) => {
+                error!("BPF compile error on {}: {}", interface, e);
+                return;
+            }
+        };
+        let instructions = program.get_instructions();
+        // SAFETY: pcap's BpfInstruction is repr(transparent) over bpf_insn, which has the same
+        // layout as the kernel's sock_filter (u16 code, u8 jt, u8 jf, u32 k).
+        let filter = unsafe {
+            std::slice::from_raw_parts(instructions.as_ptr() as *const libc::sock_filter, instructions.len())
+        };
+
+        let name = match CString::new(interface.as_str()) {
+            Ok(name) => name,
+            Err(_) => return,
+        };
+        let ifindex = unsafe { libc::if_nametoindex(name.as_ptr()) };
+        if ifindex == 0 {
+            error!("Device {} not found in system", interface);
+            return;
+        }
+        let group = (std::process::id() as u16) ^ (ifindex as u16);
+
+        let mut rings = Vec::with_capacity(workers);
+        for _ in 0..workers.max(1) {
+            match Ring::open(ifindex as i32, filter, ring_mb.max(1) as u32, group) {
+                Ok(ring) => rings.push(ring),
+                Err(e) => {
+                    error!("Failed to set up AF_PACKET ring on {}: {}", interface, e);
+                    return;
+                }
+            }
+        }
+
+        info!("AF_PACKET sniffer active on {} ({} workers, {} MB ring each)", interface, rings.len(), ring_mb);
+
+        let stop = AtomicBool::new(false);
+        thread::scope(|scope| {
+            let handles: Vec<_> = rings.iter()
+                .map(|ring| scope.spawn(|| self.run_ring(ring, linktype, &stop, &tx)))
+                .collect();
+
+            let mut last_report = Instant::now();
+            while !handles.iter().all(|h| h.is_finished()) {
+                thread::sleep(Duration::from_secs(1));
+                if last_report.elapsed() >= STATS_INTERVAL {
+                    last_report = Instant::now();
+                    Self::report_kernel_stats(&interface, &rings);
+                }
+            }
+            Self::report_kernel_stats(&interface, &rings);
+        });
+    }
+
+    fn run_ring(&self, ring: &Ring, linktype: Linktype, stop: &AtomicBool, tx: &mpsc::Sender<Discovery>) {
+        let mut frame = Vec::new();
+        let mut block = 0;
+
+        while !stop.load(Ordering::Relaxed) {
+            // SAFETY: every block lies within the mapping and starts with a block descriptor
+            // that the kernel owns until it sets TP_STATUS_USER.
+            unsafe {
+                let desc = ring.block(block);
+                let status = ptr::addr_of_mut!((*desc).hdr.bh1.block_status);
+                if ptr::read_volatile(status) & libc::TP_STATUS_USER == 0 {
+                    ring.wait();
+                    continue;
+                }
+                fence(Ordering::Acquire);
+
+                let bh1 = &(*desc).hdr.bh1;
+                let mut pkt = (desc as *const u8).add(bh1.offset_to_first_pkt as usize);
+                for _ in 0..bh1.num_pkts {
+                    let hdr = &*(pkt as *const libc::tpacket3_hdr);
+                    let data = std::slice::from_raw_parts(pkt.add(hdr.tp_mac as usize), hdr.tp_snaplen as usize);
+                    let data = Self::restore_vlan_tag(hdr, data, linktype, &mut frame);
+                    pkt = pkt.add(hdr.tp_next_offset as usize);
+
+                    if let Some(mut discovery) = self.process_frame(linktype, data) {
+                        discovery.stamp(DateTime::from_timestamp(i64::from(hdr.tp_sec), hdr.tp_nsec)
+                            .unwrap_or_else(Utc::now));
+                        if self.is_repeat_beacon(&discovery) {
+                            continue;
+                        }
+                        self.keep_evidence(&mut discovery, linktype, data, hdr.tp_len);
+                        if !self.emit(discovery, tx) {
+                            stop.store(true, Ordering::Relaxed);
+                        }
+                    }
+                }
+
+                fence(Ordering::Release);
+                ptr::write_volatile(status, libc::TP_STATUS_KERNEL);
+            }
+            block = (block + 1) % ring.block_count;
+        }
+    }
+
+    /// The kernel strips the (outer) 802.1Q tag into the packet header; put it back so VLAN
+    /// detection sees the same frame libpcap would deliver.
+    fn restore_vlan_tag<'a>(hdr: &libc::tpacket3_hdr, data: &'a [u8], linktype: Linktype, frame: &'a mut Vec<u8>) -> &'a [u8] {
+        if linktype != Linktype::ETHERNET || hdr.tp_status & libc::TP_STATUS_VLAN_VALID == 0 || data.len() < 12 {
+            return data;
+        }
+        let tpid = if hdr.tp_status & libc::TP_STATUS_VLAN_TPID_VALID != 0 { hdr.hv1.tp_vlan_tpid } else { 0x8100 };
+
+        frame.clear();
+        frame.extend_from_slice(&data[..12]);
+        frame.extend_from_slice(&tpid.to_be_bytes());
+        frame.extend_from_slice(&(hdr.hv1.tp_vlan_tci as u16).to_be_bytes());
+        frame.extend_from_slice(&data[12..]);
+        frame
+    }
+
+    fn afpacket_linktype(interface: &str) -> io::Result<Linktype> {
+        let hardware = std::fs::read_to_string(format!("/sys/class/net/{}/type", interface))?;
+        match hardware.trim().parse::<u32>() {
+            Ok(ARPHRD_ETHER) => Ok(Linktype::ETHERNET),
+            Ok(ARPHRD_IEEE80211_RADIOTAP) => Ok(Linktype::IEEE802_11_RADIOTAP),
+            Ok(ARPHRD_NONE) => Ok(Linktype::RAW),
+            _ => Err(io::Error::other(format!("unsupported hardware type {}; use the pcap backend", hardware.trim()))),
+        }
+    }
+
+    /// Reading PACKET_STATISTICS resets the counters, so each report covers the last interval.
+    fn report_kernel_stats(interface: &str, rings: &[Ring]) {
+        let (mut packets, mut drops, mut freezes) = (0u64, 0u64, 0u64);
+        for ring in rings {
+            match ring.stats() {
+                Ok(stats) => {
+                    packets += u64::from(stats.tp_packets);
+                    drops += u64::from(stats.tp_drops);
+                    freezes += u64::from(stats.tp_freeze_q_cnt);
+                }
+                Err(e) => warn!("Failed to read AF_PACKET statistics on {}: {}", interface, e),
+            }
+        }
+
+        if drops > 0 {
+            warn!("Kernel dropped {} of {} packets on {} (ring full {} times); consider more workers or a larger --ring-mb",
+                drops, packets + drops, interface, freezes);
+        } else {
+            info!("AF_PACKET {}: {} packets, no kernel drops", interface, packets);
+        }
+    }
+}
+
+/// One AF_PACKET socket with its mmap'ed TPACKET_V3 receive ring.
+struct Ring {
+    fd: OwnedFd,
+    map: *mut u8,
+    map_len: usize,
+    block_count: usize,
+}
+
+// SAFETY: the mapping is only touched by the worker that owns the ring; other threads only
+// read socket statistics through the fd.
+unsafe impl Send for Ring {}
+unsafe impl Sync for Ring {}
+
+impl Ring {
+    fn open(ifindex: i32, filter: &[libc::sock_filter], block_count: u32, group: u16) -> io::Result<Ring> {
+        // Opened with protocol 0 so nothing is queued until the socket is bound to the interface.
+        let raw = unsafe { libc::socket(libc::AF_PACKET, libc::SOCK_RAW, 0) };
+        if raw < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: `raw` is a freshly created socket that nothing else owns.
+        let fd = unsafe { OwnedFd::from_raw_fd(raw) };
+
+        set_option(&fd, libc::PACKET_VERSION, &(libc::tpacket_versions::TPACKET_V3 as i32))?;
+
+        let request = libc::tpacket_req3 {
+            tp_block_size: BLOCK_SIZE,
+            tp_block_nr: block_count,
+            tp_frame_size: FRAME_SIZE,
+            tp_frame_nr: BLOCK_SIZE / FRAME_SIZE * block_count,
+            tp_retire_blk_tov: BLOCK_TIMEOUT_MS,
+            tp_sizeof_priv: 0,
+            tp_feature_req_word: 0,
+        };
+        set_option(&fd, libc::PACKET_RX_RING, &request)?;
+
+        let map_len = BLOCK_SIZE as usize * block_count as usize;
+        let map = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                map_len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                fd.as_raw_fd(),
+                0,
+            )
+        };
+        if map == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        let ring = Ring { fd, map: map as *mut u8, map_len, block_count: block_count as usize };
+
+        let program = libc::sock_fprog { len: filter.len() as u16, filter: filter.as_ptr() as *mut libc::sock_filter };
+        if unsafe {
+            libc::setsockopt(
+                ring.fd.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_ATTACH_FILTER,
+                &program as *const _ as *const libc::c_void,
+                mem::size_of::<libc::sock_fprog>() as libc::socklen_t,
+            )
+        } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let mut address: libc::sockaddr_ll = unsafe { mem::zeroed() };
+        address.sll_family = libc::AF_PACKET as u16;
+        address.sll_protocol = (libc::ETH_P_ALL as u16).to_be();
+        address.sll_ifindex = ifindex;
+        if unsafe {
+            libc::bind(
+                ring.fd.as_raw_fd(),
+                &address as *const _ as *const libc::sockaddr,
+                mem::size_of::<libc::sockaddr_ll>() as libc::socklen_t,
+            )
+        } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let membership = libc::packet_mreq {
+            mr_ifindex: ifindex,
+            mr_type: libc::PACKET_MR_PROMISC as u16,
+            mr_alen: 0,
+            mr_address: [0; 8],
+        };
+        set_option(&ring.fd, libc::PACKET_ADD_MEMBERSHIP, &membership)?;
+
+        let fanout = u32::from(group) | ((libc::PACKET_FANOUT_HASH | libc::PACKET_FANOUT_FLAG_DEFRAG) << 16);
+        set_option(&ring.fd, libc::PACKET_FANOUT, &fanout)?;
+
+        Ok(ring)
+    }
+
+    fn block(&self, index: usize) -> *mut libc::tpacket_block_desc {
+        // SAFETY: index < block_count, so the offset stays inside the mapping.
+        unsafe { self.map.add(index * BLOCK_SIZE as usize) as *mut libc::tpacket_block_desc }
+    }
+
+    /// Sleeps until the kernel retires a block, or a second passes so a stop is noticed.
+    fn wait(&self) {
+        let mut pfd = libc::pollfd { fd: self.fd.as_raw_fd(), events: libc::POLLIN | libc::POLLERR, revents: 0 };
+        unsafe { libc::poll(&mut pfd, 1, 1000) };
+    }
+
+    fn stats(&self) -> io::Result<libc::tpacket_stats_v3> {
+        let mut stats: libc::tpacket_stats_v3 = unsafe { mem::zeroed() };
+        let mut len = mem::size_of::<libc::tpacket_stats_v3>() as libc::socklen_t;
+        if unsafe {
+            libc::getsockopt(
+                self.fd.as_raw_fd(),
+                libc::SOL_PACKET,
+                libc::PACKET_STATISTICS,
+                &mut stats as *mut _ as *mut libc::c_void,
+                &mut len,
+            )
+        } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(stats)
+    }
+}
+
+impl Drop for Ring {
+    fn drop(&mut self) {
+        unsafe { libc::munmap(self.map as *mut libc::c_void, self.map_len) };
+    }
+}
+
+fn set_option<T>(fd: &OwnedFd, option: libc::c_int, value: &T) -> io::Result<()> {
+    let ret = unsafe {
+        libc::setsockopt(
+            fd.as_raw_fd(),
+            libc::SOL_PACKET,
+            option,
+            value as *const T as *const libc::c_void,
+            mem::size_of::<T>() as libc::socklen_t,
+        )
+    };
+    if ret < 0 { Err(io::Error::last_os_error()) } else { Ok(()) }
+}
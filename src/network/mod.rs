@@ -1 +1,6 @@
+#[cfg(target_os = "linux")]
+pub mod afpacket;
+pub mod offline;
+pub mod protocols;
+pub mod remote;
 pub mod sniffer;
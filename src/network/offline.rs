@@ -0,0 +1,182 @@
+use crate::domain::models::Discovery;
+use crate::network::sniffer::Sniffer;
+use pcap::{Capture, Precision};
+use std::os::unix::fs::FileTypeExt;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+
+impl Sniffer {
+    /// Runs the discovery pipeline over pcap/pcapng files, in order, stamping everything with
+    /// the capture time. Unlike a live source nothing is dropped: the reader waits for the
+    /// sync loop when the channel is full.
+    pub fn read_files(self, files: Vec<PathBuf>, tx: mpsc::Sender<Discovery>) {
+        for file in files {
+            let mut cap = match Capture::from_file_with_precision(&file, Precision::Nano) {
+                Ok(c) => c,
+                Err(e) => {
+                    error!("Failed to open capture file {}: {}", file.display(), e);
+                    continue;
+                }
+            };
+
+            let datalink = cap.get_datalink();
+            if let Err(e) = cap.filter(&Self::filter_for(datalink), true) {
+                warn!("BPF filter error on {}: {}", file.display(), e);
+            }
+
+            info!("Reading {}", file.display());
+            let mut packets = 0u64;
+
+            loop {
+                let packet = match cap.next_packet() {
+                    Ok(p) => p,
+                    Err(pcap::Error::NoMorePackets) => break,
+                    Err(e) => {
+                        error!("Read error in {} after {} packets: {}", file.display(), packets, e);
+                        break;
+                    }
+                };
+                packets += 1;
+
+                if let Some(mut discovery) = self.process_frame(datalink, packet.data) {
+                    discovery.stamp(Self::capture_time(packet.header));
+                    if self.is_repeat_beacon(&discovery) {
+                        continue;
+                    }
+                    self.keep_evidence(&mut discovery, datalink, packet.data, packet.header.len);
+                    if tx.blocking_send(self.build_discovery(discovery)).is_err() {
+                        return;
+                    }
+                }
+            }
+
+            info!("Finished {}: {} matching packets", file.display(), packets);
+        }
+    }
+
+    /// `-` (stdin) and named pipes deliver packets as they are captured elsewhere, e.g.
+    /// `ssh sensor tcpdump -U -w - | field_watcher -r -`, so they are read like a live source.
+    pub fn is_stream(path: &Path) -> bool {
+        path.as_os_str() == "-"
+            || std::fs::metadata(path).is_ok_and(|m| m.file_type().is_fifo())
+    }
+
+    /// Reads a pcap stream like a live interface: discoveries are dropped rather than stalling
+    /// the writer when the sync loop lags. A named pipe is reopened whenever its writer goes
+    /// away, so a restarted tcpdump is picked up; stdin ends the source at EOF.
+    pub fn read_stream(self, path: PathBuf, tx: mpsc::Sender<Discovery>) {
+        let stdin = path.as_os_str() == "-";
+        let name = if stdin { "stdin".to_string() } else { path.display().to_string() };
+
+        loop {
+            // Opening a FIFO blocks until a writer shows up and sends the pcap header.
+            let mut cap = match Capture::from_file_with_precision(&path, Precision::Nano) {
+                Ok(c) => c,
+                Err(e) if stdin => {
+                    error!("Failed to read a pcap stream from stdin: {}", e);
+                    return;
+                }
+                Err(e) => {
+                    warn!("Failed to open pcap stream {}: {}; retrying", name, e);
+                    thread::sleep(Duration::from_secs(1));
+                    continue;
+                }
+            };
+
+            let datalink = cap.get_datalink();
+            if let Err(e) = cap.filter(&Self::filter_for(datalink), true) {
+                warn!("BPF filter error on {}: {}", name, e);
+            }
+
+            info!("Reading pcap stream from {}", name);
+
+            loop {
+                let packet = match cap.next_packet() {
+                    Ok(p) => p,
+                    Err(pcap::Error::NoMorePackets) => break,
+                    Err(e) => {
+                        error!("Stream error on {}: {}", name, e);
+                        break;
+                    }
+                };
+
+                if let Some(mut discovery) = self.process_frame(datalink, packet.data) {
+                    discovery.stamp(Self::capture_time(packet.header));
+                    if self.is_repeat_beacon(&discovery) {
+                        continue;
+                    }
+                    self.keep_evidence(&mut discovery, datalink, packet.data, packet.header.len);
+                    if !self.emit(discovery, &tx) {
+                        return;
+                    }
+                }
+            }
+
+            if stdin {
+                info!("End of pcap stream on stdin");
+                return;
+            }
+            info!("Writer closed {}; waiting for the next one", name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Utc};
+    use pcap::Linktype;
+
+    /// MNDP and Ubiquiti announcements on Ethernet, five seconds apart.
+    const CAPTURE: &[u8] = include_bytes!("../../tests/fixtures/vendor-discovery.pcap");
+
+    /// Walks a classic little endian pcap file without going through libpcap.
+    fn records(file: &[u8]) -> Vec<(DateTime<Utc>, &[u8])> {
+        assert_eq!(&file[0..4], &[0xd4, 0xc3, 0xb2, 0xa1]);
+        let word = |pos: usize| u32::from_le_bytes(file[pos..pos + 4].try_into().unwrap());
+        let mut records = Vec::new();
+        let mut pos = 24;
+        while pos + 16 <= file.len() {
+            let time = DateTime::from_timestamp(i64::from(word(pos)), word(pos + 4) * 1000).unwrap();
+            let len = word(pos + 8) as usize;
+            records.push((time, &file[pos + 16..pos + 16 + len]));
+            pos += 16 + len;
+        }
+        records
+    }
+
+    fn booted_at(discovery: &crate::network::sniffer::RawDiscovery) -> Option<&str> {
+        discovery.attributes.iter().find(|(k, _)| k == "booted_at").map(|(_, v)| v.as_str())
+    }
+
+    #[test]
+    fn vendor_discovery_boot_time() {
+        let sniffer = Sniffer::new(String::new());
+        let discoveries: Vec<_> = records(CAPTURE)
+            .into_iter()
+            .filter_map(|(time, data)| {
+                let mut discovery = sniffer.process_frame(Linktype::ETHERNET, data)?;
+                discovery.stamp(time);
+                Some(discovery)
+            })
+            .collect();
+        assert_eq!(discoveries.len(), 2);
+
+        let mndp = &discoveries[0];
+        assert_eq!(mndp.method, "MNDP");
+        assert_eq!(mndp.mac, "48:8F:5A:01:02:03");
+        assert_eq!(mndp.ip, "10.0.0.1");
+        assert_eq!(mndp.hostname.as_deref(), Some("core-1"));
+        assert_eq!(mndp.model.as_deref(), Some("RB5009"));
+        assert_eq!(booted_at(mndp), Some("2024-04-30 12:00:00"));
+
+        let ubnt = &discoveries[1];
+        assert_eq!(ubnt.method, "Ubiquiti");
+        assert_eq!(ubnt.mac, "24:5A:4C:01:02:03");
+        assert_eq!(ubnt.firmware.as_deref(), Some("XM.v6.3.11"));
+        assert_eq!(booted_at(ubnt), Some("2024-05-01 11:00:05"));
+    }
+}
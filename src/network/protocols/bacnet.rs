@@ -0,0 +1,154 @@
+use std::net::Ipv4Addr;
+
+/// A BACnet/IP unconfirmed request (I-Am, I-Have or Who-Is) with its addressing.
+#[derive(Debug, Default)]
+pub struct BacnetPacket {
+    pub service: &'static str,
+    /// Original B/IP address of a Forwarded-NPDU relayed by a BBMD.
+    pub forwarded_from: Option<Ipv4Addr>,
+    /// SNET/SADR when the NPDU crossed a BACnet router.
+    pub source_network: Option<(u16, Vec<u8>)>,
+    pub device_instance: Option<u32>,
+    pub max_apdu: Option<u32>,
+    pub segmentation: Option<&'static str>,
+    pub vendor_id: Option<u32>,
+    pub object_name: Option<String>,
+}
+
+const BVLC_TYPE: u8 = 0x81;
+const BVLC_FORWARDED_NPDU: u8 = 0x04;
+const OBJECT_TYPE_DEVICE: u32 = 8;
+
+pub fn parse(payload: &[u8]) -> Option<BacnetPacket> {
+    // BVLC: type, function, length(2)
+    if payload.len() < 4 || payload[0] != BVLC_TYPE {
+        return None;
+    }
+    let mut packet = BacnetPacket::default();
+    let mut pos = 4;
+    if payload[1] == BVLC_FORWARDED_NPDU {
+        let addr = payload.get(4..10)?;
+        packet.forwarded_from = Some(Ipv4Addr::new(addr[0], addr[1], addr[2], addr[3]));
+        pos = 10;
+    }
+
+    // NPDU: version, control, [DNET, DLEN, DADR], [SNET, SLEN, SADR], [hop count]
+    let npdu = payload.get(pos..)?;
+    if npdu.len() < 2 || npdu[0] != 0x01 {
+        return None;
+    }
+    let control = npdu[1];
+    if control & 0x80 != 0 {
+        // Network layer message, no APDU.
+        return None;
+    }
+    let mut p = 2;
+    if control & 0x20 != 0 {
+        let dlen = *npdu.get(p + 2)? as usize;
+        p += 3 + dlen;
+    }
+    if control & 0x08 != 0 {
+        let snet = u16::from_be_bytes([*npdu.get(p)?, *npdu.get(p + 1)?]);
+        let slen = *npdu.get(p + 2)? as usize;
+        let sadr = npdu.get(p + 3..p + 3 + slen)?.to_vec();
+        packet.source_network = Some((snet, sadr));
+        p += 3 + slen;
+    }
+    if control & 0x20 != 0 {
+        p += 1;
+    }
+
+    // APDU: PDU type 1 = unconfirmed request, followed by the service choice.
+    let apdu = npdu.get(p..)?;
+    if apdu.len() < 2 || apdu[0] >> 4 != 1 {
+        return None;
+    }
+    let mut tags = TagReader { data: &apdu[2..] };
+
+    match apdu[1] {
+        0 => {
+            packet.service = "I-Am";
+            packet.device_instance = device_instance(tags.next()?);
+            packet.max_apdu = tags.next().map(unsigned);
+            packet.segmentation = tags.next().map(|v| match unsigned(v) {
+                0 => "both",
+                1 => "transmit",
+                2 => "receive",
+                _ => "none",
+            });
+            packet.vendor_id = tags.next().map(unsigned);
+        }
+        1 => {
+            packet.service = "I-Have";
+            packet.device_instance = device_instance(tags.next()?);
+            let _object = tags.next();
+            // CharacterString: encoding octet, then the characters.
+            packet.object_name = tags.next()
+                .and_then(|v| v.get(1..))
+                .map(|s| String::from_utf8_lossy(s).trim().to_string());
+        }
+        8 => packet.service = "Who-Is",
+        _ => return None,
+    }
+
+    Some(packet)
+}
+
+fn unsigned(value: &[u8]) -> u32 {
+    value.iter().take(4).fold(0, |acc, &b| (acc << 8) | u32::from(b))
+}
+
+fn device_instance(value: &[u8]) -> Option<u32> {
+    let id = u32::from_be_bytes(value.try_into().ok()?);
+    (id >> 22 == OBJECT_TYPE_DEVICE).then_some(id & 0x3F_FFFF)
+}
+
+/// Walks application-tagged values, yielding their content octets.
+struct TagReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> TagReader<'a> {
+    fn next(&mut self) -> Option<&'a [u8]> {
+        let tag = *self.data.first()?;
+        let (len, header) = match tag & 0x07 {
+            5 => (*self.data.get(1)? as usize, 2),
+            n => (n as usize, 1),
+        };
+        let value = self.data.get(header..header + len)?;
+        self.data = &self.data[header + len..];
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn i_am() {
+        let payload = [
+            0x81, 0x0b, 0x00, 0x14, 0x01, 0x20, 0xff, 0xff, 0x00, 0xff, // BVLC, NPDU to all networks
+            0x10, 0x00, 0xc4, 0x02, 0x00, 0x04, 0xd2, 0x22, 0x05, 0xc4, 0x91, 0x03, 0x21, 0x05,
+        ];
+        let parsed = parse(&payload).unwrap();
+        assert_eq!(parsed.service, "I-Am");
+        assert_eq!(parsed.device_instance, Some(1234));
+        assert_eq!(parsed.max_apdu, Some(1476));
+        assert_eq!(parsed.segmentation, Some("none"));
+        assert_eq!(parsed.vendor_id, Some(5));
+    }
+
+    #[test]
+    fn forwarded_from_routed_network() {
+        let payload = [
+            0x81, 0x04, 0x00, 0x1a, 10, 0, 5, 7, 0xba, 0xc0, // forwarded from 10.0.5.7:47808
+            0x01, 0x08, 0x00, 0x05, 0x01, 0x2a, // SNET 5, SADR 0x2a
+            0x10, 0x08, // Who-Is
+        ];
+        let parsed = parse(&payload).unwrap();
+        assert_eq!(parsed.service, "Who-Is");
+        assert_eq!(parsed.forwarded_from, Some(Ipv4Addr::new(10, 0, 5, 7)));
+        assert_eq!(parsed.source_network, Some((5, vec![0x2a])));
+    }
+}
@@ -0,0 +1,188 @@
+/// A Browser protocol announcement carried in a NetBIOS datagram (UDP 138) to \MAILSLOT\BROWSE.
+#[derive(Debug)]
+pub struct BrowserAnnouncement {
+    pub kind: &'static str,
+    /// Server name, or the workgroup/domain name for DomainAnnouncement.
+    pub name: String,
+    /// NetBIOS name the datagram was addressed to (the workgroup for host announcements).
+    pub destination: Option<String>,
+    pub os_version: (u8, u8),
+    pub server_type: u32,
+    /// Free text for host announcements; the master browser's name for DomainAnnouncement.
+    pub comment: Option<String>,
+}
+
+const SV_TYPE_SQLSERVER: u32 = 0x0000_0004;
+const SV_TYPE_DOMAIN_CTRL: u32 = 0x0000_0008;
+const SV_TYPE_DOMAIN_BAKCTRL: u32 = 0x0000_0010;
+const SV_TYPE_TIME_SOURCE: u32 = 0x0000_0020;
+const SV_TYPE_PRINTQ_SERVER: u32 = 0x0000_0200;
+const SV_TYPE_SERVER_UNIX: u32 = 0x0000_0800;
+const SV_TYPE_SERVER_NT: u32 = 0x0000_8000;
+const SV_TYPE_MASTER_BROWSER: u32 = 0x0004_0000;
+const SV_TYPE_DOMAIN_MASTER: u32 = 0x0008_0000;
+
+/// Parses the datagram header, both encoded NetBIOS names, the SMB Transaction and the
+/// browser frame it carries.
+pub fn parse(payload: &[u8]) -> Option<BrowserAnnouncement> {
+    // msg type, flags, id(2), source ip(4), source port(2), length(2), offset(2)
+    if payload.len() < 14 || !matches!(payload[0], 0x10..=0x12) {
+        return None;
+    }
+    let (_, rest) = netbios_name(&payload[14..])?;
+    let (destination, smb) = netbios_name(rest)?;
+
+    // SMB header(32), word count, then the Transaction parameter words.
+    if smb.len() < 33 || &smb[0..4] != b"\xFFSMB" || smb[4] != 0x25 || smb[32] != 17 {
+        return None;
+    }
+    let data_offset = u16::from_le_bytes([*smb.get(57)?, *smb.get(58)?]) as usize;
+    let frame = smb.get(data_offset..)?;
+
+    // opcode, update count, periodicity(4), name(16), os major, os minor, server type(4),
+    // browser version(2), signature(2), comment
+    if frame.len() < 32 {
+        return None;
+    }
+    let kind = match frame[0] {
+        0x01 => "HostAnnouncement",
+        0x0C => "DomainAnnouncement",
+        0x0F => "LocalMasterAnnouncement",
+        _ => return None,
+    };
+    let comment = frame[32..].split(|&b| b == 0).next()
+        .map(|s| String::from_utf8_lossy(s).trim().to_string())
+        .filter(|s| !s.is_empty());
+
+    Some(BrowserAnnouncement {
+        kind,
+        name: text(&frame[6..22])?,
+        destination,
+        os_version: (frame[22], frame[23]),
+        server_type: u32::from_le_bytes([frame[24], frame[25], frame[26], frame[27]]),
+        comment,
+    })
+}
+
+/// Coarse OS family from the announced version, server flags and comment.
+pub fn os_guess(announcement: &BrowserAnnouncement) -> Option<String> {
+    let samba = announcement.server_type & SV_TYPE_SERVER_UNIX != 0
+        || announcement.comment.as_deref().is_some_and(|c| c.to_ascii_lowercase().contains("samba"));
+    if samba {
+        return Some("Samba (Linux/Unix)".to_string());
+    }
+
+    let server = announcement.server_type & SV_TYPE_SERVER_NT != 0;
+    let name = match (announcement.os_version, server) {
+        ((4, 0), _) => "Windows NT 4.0 / 9x",
+        ((5, 0), false) => "Windows 2000 Professional",
+        ((5, 0), true) => "Windows 2000 Server",
+        ((5, 1), _) => "Windows XP",
+        ((5, 2), _) => "Windows Server 2003",
+        ((6, 0), false) => "Windows Vista",
+        ((6, 0), true) => "Windows Server 2008",
+        ((6, 1), false) => "Windows 7",
+        ((6, 1), true) => "Windows Server 2008 R2",
+        ((6, 2), false) => "Windows 8",
+        ((6, 2), true) => "Windows Server 2012",
+        ((6, 3), false) => "Windows 8.1",
+        ((6, 3), true) => "Windows Server 2012 R2",
+        ((10, 0), false) => "Windows 10/11",
+        ((10, 0), true) => "Windows Server 2016+",
+        ((major, minor), _) => return Some(format!("Windows {}.{}", major, minor)),
+    };
+    Some(name.to_string())
+}
+
+pub fn server_roles(server_type: u32) -> Vec<&'static str> {
+    [
+        (SV_TYPE_DOMAIN_CTRL | SV_TYPE_DOMAIN_BAKCTRL, "domain-controller"),
+        (SV_TYPE_SQLSERVER, "sql-server"),
+        (SV_TYPE_PRINTQ_SERVER, "print-server"),
+        (SV_TYPE_TIME_SOURCE, "time-source"),
+        (SV_TYPE_MASTER_BROWSER | SV_TYPE_DOMAIN_MASTER, "master-browser"),
+    ]
+    .into_iter()
+    .filter(|(mask, _)| server_type & mask != 0)
+    .map(|(_, role)| role)
+    .collect()
+}
+
+/// Decodes a first-level encoded NetBIOS name (length 0x20, 32 half-byte chars, root label).
+fn netbios_name(data: &[u8]) -> Option<(Option<String>, &[u8])> {
+    if data.len() < 34 || data[0] != 0x20 {
+        return None;
+    }
+    let raw: Vec<u8> = data[1..33].chunks(2)
+        .map(|pair| (pair[0].wrapping_sub(b'A') << 4) | (pair[1].wrapping_sub(b'A') & 0x0F))
+        .collect();
+    // The 16th byte is the NetBIOS suffix (service type).
+    Some((text(&raw[..15]), &data[34..]))
+}
+
+fn text(value: &[u8]) -> Option<String> {
+    let s = String::from_utf8_lossy(value).trim_end_matches('\0').trim().to_string();
+    (!s.is_empty()).then_some(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encoded_name(name: &str, suffix: u8) -> Vec<u8> {
+        let mut raw = format!("{:<15}", name).into_bytes();
+        raw.push(suffix);
+        let mut out = vec![0x20];
+        for b in raw {
+            out.extend([b'A' + (b >> 4), b'A' + (b & 0x0f)]);
+        }
+        out.push(0x00);
+        out
+    }
+
+    /// A HostAnnouncement from FILESRV01 to the WORKGROUP browse mailslot.
+    fn host_announcement(os: (u8, u8), server_type: u32, comment: &str) -> Vec<u8> {
+        let mut frame = vec![0x01, 0x00, 0x60, 0xea, 0x00, 0x00];
+        let mut name = b"FILESRV01".to_vec();
+        name.resize(16, 0);
+        frame.extend(name);
+        frame.extend([os.0, os.1]);
+        frame.extend(server_type.to_le_bytes());
+        frame.extend([0x0f, 0x01, 0x55, 0xaa]);
+        frame.extend(comment.as_bytes());
+        frame.push(0);
+
+        let data_offset = 86u16;
+        let mut smb = b"\xffSMB\x25".to_vec();
+        smb.resize(32, 0);
+        smb.push(17);
+        smb.resize(57, 0);
+        smb.extend(data_offset.to_le_bytes());
+        smb.resize(data_offset as usize, 0);
+        smb.extend(frame);
+
+        let mut payload = vec![0x11, 0x02, 0x00, 0x01, 192, 168, 1, 5, 0x00, 0x8a, 0x00, 0x00, 0x00, 0x00];
+        payload.extend(encoded_name("FILESRV01", 0x00));
+        payload.extend(encoded_name("WORKGROUP", 0x1d));
+        payload.extend(smb);
+        payload
+    }
+
+    #[test]
+    fn windows_host_announcement() {
+        let parsed = parse(&host_announcement((10, 0), 0x0000_9203, "")).unwrap();
+        assert_eq!(parsed.kind, "HostAnnouncement");
+        assert_eq!(parsed.name, "FILESRV01");
+        assert_eq!(parsed.destination.as_deref(), Some("WORKGROUP"));
+        assert_eq!(parsed.comment, None);
+        assert_eq!(os_guess(&parsed).as_deref(), Some("Windows Server 2016+"));
+        assert_eq!(server_roles(parsed.server_type), vec!["print-server"]);
+    }
+
+    #[test]
+    fn samba_host_announcement() {
+        let parsed = parse(&host_announcement((4, 9), 0x0000_0a03, "Samba 4.19.5-Ubuntu")).unwrap();
+        assert_eq!(parsed.comment.as_deref(), Some("Samba 4.19.5-Ubuntu"));
+        assert_eq!(os_guess(&parsed).as_deref(), Some("Samba (Linux/Unix)"));
+    }
+}
@@ -0,0 +1,104 @@
+/// A resource advertised in a CoAP `/.well-known/core` (CoRE Link Format) response.
+#[derive(Debug)]
+pub struct CoapResource {
+    pub path: String,
+    pub resource_type: Option<String>,
+    pub interface: Option<String>,
+}
+
+const CODE_CONTENT: u8 = 0x45;
+const OPTION_CONTENT_FORMAT: u16 = 12;
+const FORMAT_LINK_FORMAT: u16 = 40;
+
+/// Parses a 2.05 Content response carrying application/link-format.
+pub fn parse_link_format(payload: &[u8]) -> Option<Vec<CoapResource>> {
+    // version/type/token length, code, message id(2), token
+    if payload.len() < 4 || payload[0] >> 6 != 1 || payload[1] != CODE_CONTENT {
+        return None;
+    }
+    let mut pos = 4 + (payload[0] & 0x0F) as usize;
+
+    let mut option_number = 0u16;
+    let mut link_format = false;
+    while let Some(&byte) = payload.get(pos) {
+        if byte == 0xFF {
+            pos += 1;
+            break;
+        }
+        pos += 1;
+        let delta = extended(byte >> 4, payload, &mut pos)?;
+        let len = extended(byte & 0x0F, payload, &mut pos)? as usize;
+        let value = payload.get(pos..pos + len)?;
+        option_number = option_number.checked_add(delta)?;
+        if option_number == OPTION_CONTENT_FORMAT {
+            link_format = value.iter().fold(0u16, |acc, &b| (acc << 8) | u16::from(b)) == FORMAT_LINK_FORMAT;
+        }
+        pos += len;
+    }
+    if !link_format {
+        return None;
+    }
+
+    // </path>;rt="type";if="iface",</other>;...
+    let body = String::from_utf8_lossy(payload.get(pos..)?);
+    let resources: Vec<CoapResource> = body.split(",<")
+        .filter_map(|link| {
+            let link = link.trim().trim_start_matches('<');
+            let (path, params) = link.split_once('>')?;
+            let param = |name: &str| params.split(';')
+                .filter_map(|p| p.trim().split_once('='))
+                .find(|(k, _)| *k == name)
+                .map(|(_, v)| v.trim_matches('"').to_string());
+            Some(CoapResource {
+                path: path.to_string(),
+                resource_type: param("rt"),
+                interface: param("if"),
+            })
+        })
+        .collect();
+    (!resources.is_empty()).then_some(resources)
+}
+
+/// Option delta/length nibbles 13 and 14 are followed by 1 or 2 extension bytes.
+fn extended(nibble: u8, data: &[u8], pos: &mut usize) -> Option<u16> {
+    match nibble {
+        13 => {
+            let value = u16::from(*data.get(*pos)?) + 13;
+            *pos += 1;
+            Some(value)
+        }
+        14 => {
+            let value = u16::from_be_bytes([*data.get(*pos)?, *data.get(*pos + 1)?]).checked_add(269)?;
+            *pos += 2;
+            Some(value)
+        }
+        15 => None,
+        n => Some(u16::from(n)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn well_known_core() {
+        let mut payload = vec![0x62, CODE_CONTENT, 0x12, 0x34, 0xab, 0xcd]; // ACK, 2-byte token
+        payload.extend([0xc1, 0x28, 0xff]); // Content-Format 40
+        payload.extend(b"</sensors/temp>;rt=\"temperature-c\";if=\"sensor\",</fw>;ct=0");
+
+        let resources = parse_link_format(&payload).unwrap();
+        assert_eq!(resources.len(), 2);
+        assert_eq!(resources[0].path, "/sensors/temp");
+        assert_eq!(resources[0].resource_type.as_deref(), Some("temperature-c"));
+        assert_eq!(resources[0].interface.as_deref(), Some("sensor"));
+        assert_eq!(resources[1].path, "/fw");
+        assert_eq!(resources[1].resource_type, None);
+    }
+
+    #[test]
+    fn other_content_format() {
+        let payload = [0x60, CODE_CONTENT, 0x12, 0x34, 0xc1, 0x32, 0xff, b'{', b'}'];
+        assert!(parse_link_format(&payload).is_none());
+    }
+}
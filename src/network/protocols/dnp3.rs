@@ -0,0 +1,37 @@
+/// DNP3 data link header: start bytes 0x0564, length, control, destination and source (LE).
+#[derive(Debug)]
+pub struct Dnp3Link {
+    /// DIR bit: set on frames sent by the master.
+    pub from_master: bool,
+    pub destination: u16,
+    pub source: u16,
+}
+
+pub fn parse(payload: &[u8]) -> Option<Dnp3Link> {
+    if payload.len() < 10 || payload[0..2] != [0x05, 0x64] || payload[2] < 5 {
+        return None;
+    }
+    Some(Dnp3Link {
+        from_master: payload[3] & 0x80 != 0,
+        destination: u16::from_le_bytes([payload[4], payload[5]]),
+        source: u16::from_le_bytes([payload[6], payload[7]]),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn master_request() {
+        let payload = [0x05, 0x64, 0x0b, 0xc4, 0x0a, 0x00, 0x01, 0x00, 0x4e, 0x2b, 0xc0, 0xc1, 0x01];
+        let parsed = parse(&payload).unwrap();
+        assert!(parsed.from_master);
+        assert_eq!((parsed.destination, parsed.source), (10, 1));
+    }
+
+    #[test]
+    fn not_dnp3() {
+        assert!(parse(&[0x05, 0x65, 0x0b, 0xc4, 0x0a, 0x00, 0x01, 0x00, 0x00, 0x00]).is_none());
+    }
+}
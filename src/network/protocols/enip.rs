@@ -0,0 +1,106 @@
+/// CIP identity object as returned in an EtherNet/IP ListIdentity response.
+#[derive(Debug)]
+pub struct CipIdentity {
+    pub vendor_id: u16,
+    pub device_type: u16,
+    pub product_code: u16,
+    pub revision: (u8, u8),
+    pub serial_number: u32,
+    pub product_name: Option<String>,
+}
+
+const COMMAND_LIST_IDENTITY: u16 = 0x0063;
+const ITEM_CIP_IDENTITY: u16 = 0x000C;
+
+/// Parses the encapsulation header (little endian) and the first CIP identity item.
+pub fn parse_list_identity(payload: &[u8]) -> Option<CipIdentity> {
+    // command(2), length(2), session(4), status(4), sender context(8), options(4)
+    if payload.len() < 26 {
+        return None;
+    }
+    let command = u16::from_le_bytes([payload[0], payload[1]]);
+    let status = u32::from_le_bytes([payload[8], payload[9], payload[10], payload[11]]);
+    if command != COMMAND_LIST_IDENTITY || status != 0 {
+        return None;
+    }
+
+    let item_count = u16::from_le_bytes([payload[24], payload[25]]);
+    let mut pos = 26;
+    for _ in 0..item_count {
+        let header = payload.get(pos..pos + 4)?;
+        let item_type = u16::from_le_bytes([header[0], header[1]]);
+        let item_len = u16::from_le_bytes([header[2], header[3]]) as usize;
+        let item = payload.get(pos + 4..pos + 4 + item_len)?;
+
+        // protocol version(2), socket address(16), then the identity attributes
+        if item_type == ITEM_CIP_IDENTITY && item.len() >= 33 {
+            let name_len = item[32] as usize;
+            let product_name = item.get(33..33 + name_len)
+                .map(|b| String::from_utf8_lossy(b).trim().to_string())
+                .filter(|s| !s.is_empty());
+
+            return Some(CipIdentity {
+                vendor_id: u16::from_le_bytes([item[18], item[19]]),
+                device_type: u16::from_le_bytes([item[20], item[21]]),
+                product_code: u16::from_le_bytes([item[22], item[23]]),
+                revision: (item[24], item[25]),
+                serial_number: u32::from_le_bytes([item[28], item[29], item[30], item[31]]),
+                product_name,
+            });
+        }
+        pos += 4 + item_len;
+    }
+    None
+}
+
+/// Names for the CIP device profiles commonly found on plant floors.
+pub fn device_type_name(device_type: u16) -> Option<&'static str> {
+    match device_type {
+        0x00 => Some("Generic Device"),
+        0x02 => Some("AC Drive"),
+        0x07 => Some("General Purpose Discrete I/O"),
+        0x0C => Some("Communications Adapter"),
+        0x0E => Some("Programmable Logic Controller"),
+        0x18 => Some("Human-Machine Interface"),
+        0x2B => Some("Generic Device (keyable)"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_identity_response() {
+        let mut item = vec![0x01, 0x00, 0x00, 0x02, 0xaf, 0x12, 192, 168, 1, 20];
+        item.extend([0u8; 8]);
+        item.extend([0x01, 0x00, 0x0e, 0x00, 0x36, 0x00, 0x20, 0x0b]); // vendor, type, code, revision
+        item.extend([0x60, 0x30, 0x78, 0x56, 0x34, 0x12]); // status, serial
+        item.push(11);
+        item.extend(b"1769-L33ER ");
+        item.push(0x03); // state
+
+        let mut payload = vec![0x63, 0x00];
+        payload.extend(((item.len() + 6) as u16).to_le_bytes());
+        payload.extend([0u8; 20]);
+        payload.extend([0x01, 0x00, 0x0c, 0x00]);
+        payload.extend((item.len() as u16).to_le_bytes());
+        payload.extend(&item);
+
+        let parsed = parse_list_identity(&payload).unwrap();
+        assert_eq!(parsed.vendor_id, 1);
+        assert_eq!(device_type_name(parsed.device_type), Some("Programmable Logic Controller"));
+        assert_eq!(parsed.product_code, 0x36);
+        assert_eq!(parsed.revision, (32, 11));
+        assert_eq!(parsed.serial_number, 0x1234_5678);
+        assert_eq!(parsed.product_name.as_deref(), Some("1769-L33ER"));
+    }
+
+    #[test]
+    fn list_identity_request() {
+        let mut payload = vec![0x63, 0x00, 0x00, 0x00];
+        payload.extend([0u8; 20]);
+        assert!(parse_list_identity(&payload).is_none());
+    }
+}
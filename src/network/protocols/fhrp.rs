@@ -0,0 +1,271 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// Decoded first-hop redundancy hello, common to HSRP, VRRP and GLBP.
+#[derive(Debug)]
+pub struct FhrpPacket {
+    pub protocol: &'static str,
+    pub group_id: u16,
+    pub virtual_ip: Option<IpAddr>,
+    pub virtual_mac: Option<[u8; 6]>,
+    pub priority: u8,
+    pub state: &'static str,
+    /// Burned-in MAC of the speaker, when the protocol carries it (HSRPv2, GLBP).
+    pub member_mac: Option<[u8; 6]>,
+}
+
+/// HSRP hellos on UDP 1985 (v1/v2) and 2029 (IPv6).
+pub fn parse_hsrp(payload: &[u8]) -> Option<FhrpPacket> {
+    // HSRPv2 is TLV based and always starts with a 40-byte Group State TLV.
+    if payload.len() >= 2 && payload[0] == 1 && payload[1] == 40 {
+        return parse_hsrp_v2(payload);
+    }
+
+    // HSRPv1: version, opcode, state, hellotime, holdtime, priority, group, reserved, auth(8), vip(4)
+    if payload.len() < 20 || payload[0] != 0 {
+        return None;
+    }
+    let group = payload[6];
+    let state = match payload[2] {
+        0 => "initial",
+        1 => "learn",
+        2 => "listen",
+        4 => "speak",
+        8 => "standby",
+        16 => "active",
+        _ => "unknown",
+    };
+    let vip = Ipv4Addr::new(payload[16], payload[17], payload[18], payload[19]);
+
+    Some(FhrpPacket {
+        protocol: "HSRP",
+        group_id: group.into(),
+        virtual_ip: (!vip.is_unspecified()).then_some(IpAddr::V4(vip)),
+        virtual_mac: Some([0x00, 0x00, 0x0C, 0x07, 0xAC, group]),
+        priority: payload[5],
+        state,
+        member_mac: None,
+    })
+}
+
+fn parse_hsrp_v2(payload: &[u8]) -> Option<FhrpPacket> {
+    let mut pos = 0;
+    while pos + 2 <= payload.len() {
+        let tlv_type = payload[pos];
+        let tlv_len = payload[pos + 1] as usize;
+        let value = payload.get(pos + 2..pos + 2 + tlv_len)?;
+
+        // Group State TLV: version, opcode, state, ip version, group(2), identifier(6),
+        // priority(4), hellotime(4), holdtime(4), virtual ip(16)
+        if tlv_type == 1 && value.len() >= 40 {
+            let group = u16::from_be_bytes([value[4], value[5]]) & 0x0FFF;
+            let state = match value[2] {
+                0 => "disabled",
+                1 => "init",
+                2 => "learn",
+                3 => "listen",
+                4 => "speak",
+                5 => "standby",
+                6 => "active",
+                _ => "unknown",
+            };
+            let mut identifier = [0u8; 6];
+            identifier.copy_from_slice(&value[6..12]);
+            let priority = u32::from_be_bytes([value[12], value[13], value[14], value[15]]);
+
+            let (virtual_ip, virtual_mac) = if value[3] == 6 {
+                let mut octets = [0u8; 16];
+                octets.copy_from_slice(&value[24..40]);
+                let vip = Ipv6Addr::from(octets);
+                let [hi, lo] = group.to_be_bytes();
+                ((!vip.is_unspecified()).then_some(IpAddr::V6(vip)), [0x00, 0x05, 0x73, 0xA0, hi, lo])
+            } else {
+                let vip = Ipv4Addr::new(value[24], value[25], value[26], value[27]);
+                let [hi, lo] = group.to_be_bytes();
+                ((!vip.is_unspecified()).then_some(IpAddr::V4(vip)), [0x00, 0x00, 0x0C, 0x9F, 0xF0 | hi, lo])
+            };
+
+            return Some(FhrpPacket {
+                protocol: "HSRP",
+                group_id: group,
+                virtual_ip,
+                virtual_mac: Some(virtual_mac),
+                priority: priority.min(u8::MAX.into()) as u8,
+                state,
+                member_mac: Some(identifier),
+            });
+        }
+        pos += 2 + tlv_len;
+    }
+    None
+}
+
+/// VRRP advertisements (IP protocol 112). Only the master sends them.
+pub fn parse_vrrp(payload: &[u8], ipv6: bool) -> Option<FhrpPacket> {
+    if payload.len() < 8 || payload[0] & 0x0F != 1 {
+        return None;
+    }
+    let version = payload[0] >> 4;
+    if !(2..=3).contains(&version) {
+        return None;
+    }
+    let vrid = payload[1];
+    let priority = payload[2];
+
+    // v2 and v3 both place the address list at offset 8.
+    let virtual_ip = if payload[3] == 0 {
+        None
+    } else if ipv6 {
+        let octets: [u8; 16] = payload.get(8..24)?.try_into().ok()?;
+        Some(IpAddr::V6(Ipv6Addr::from(octets)))
+    } else {
+        let octets: [u8; 4] = payload.get(8..12)?.try_into().ok()?;
+        Some(IpAddr::V4(Ipv4Addr::from(octets)))
+    };
+
+    Some(FhrpPacket {
+        protocol: "VRRP",
+        group_id: vrid.into(),
+        virtual_ip,
+        virtual_mac: Some([0x00, 0x00, 0x5E, 0x00, if ipv6 { 0x02 } else { 0x01 }, vrid]),
+        priority,
+        state: if priority == 0 { "resign" } else { "master" },
+        member_mac: None,
+    })
+}
+
+/// GLBP hellos on UDP 3222.
+pub fn parse_glbp(payload: &[u8]) -> Option<FhrpPacket> {
+    // version, unknown, group(2), unknown(2), owner id(6)
+    if payload.len() < 12 || payload[0] != 1 {
+        return None;
+    }
+    let group_id = u16::from_be_bytes([payload[2], payload[3]]);
+    let mut owner = [0u8; 6];
+    owner.copy_from_slice(&payload[6..12]);
+
+    let mut packet = FhrpPacket {
+        protocol: "GLBP",
+        group_id,
+        virtual_ip: None,
+        virtual_mac: None,
+        priority: 0,
+        state: "unknown",
+        member_mac: Some(owner),
+    };
+    let mut seen_hello = false;
+
+    let mut pos = 12;
+    while pos + 2 <= payload.len() {
+        let tlv_type = payload[pos];
+        // TLV length includes the type and length octets.
+        let tlv_len = payload[pos + 1] as usize;
+        if tlv_len < 2 {
+            break;
+        }
+        let Some(value) = payload.get(pos + 2..pos + tlv_len) else { break };
+
+        match tlv_type {
+            1 if value.len() >= 22 => {
+                seen_hello = true;
+                packet.state = match value[1] {
+                    1 => "disabled",
+                    2 => "init",
+                    4 => "listen",
+                    8 => "speak",
+                    16 => "standby",
+                    32 => "active",
+                    _ => "unknown",
+                };
+                packet.priority = value[3];
+                let addr_len = value[21] as usize;
+                packet.virtual_ip = match (value[20], value.get(22..22 + addr_len)) {
+                    (1, Some(a)) if addr_len == 4 => Some(IpAddr::V4(Ipv4Addr::new(a[0], a[1], a[2], a[3]))),
+                    (2, Some(a)) if addr_len == 16 => {
+                        let octets: [u8; 16] = a.try_into().ok()?;
+                        Some(IpAddr::V6(Ipv6Addr::from(octets)))
+                    }
+                    _ => None,
+                };
+            }
+            // Request/Response TLV carries the forwarder's virtual MAC.
+            2 if value.len() >= 18 && packet.virtual_mac.is_none() => {
+                let mut vmac = [0u8; 6];
+                vmac.copy_from_slice(&value[12..18]);
+                packet.virtual_mac = Some(vmac);
+            }
+            _ => {}
+        }
+        pos += tlv_len;
+    }
+
+    seen_hello.then_some(packet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hsrp_v1_active() {
+        let payload = [
+            0x00, 0x00, 0x10, 0x03, 0x0a, 0x6e, 0x01, 0x00, // version, hello, active, times, priority 110, group 1
+            b'c', b'i', b's', b'c', b'o', 0x00, 0x00, 0x00, // authentication
+            10, 0, 0, 254,
+        ];
+        let parsed = parse_hsrp(&payload).unwrap();
+        assert_eq!(parsed.state, "active");
+        assert_eq!(parsed.priority, 110);
+        assert_eq!(parsed.virtual_ip, Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 254))));
+        assert_eq!(parsed.virtual_mac, Some([0x00, 0x00, 0x0C, 0x07, 0xAC, 0x01]));
+        assert_eq!(parsed.member_mac, None);
+    }
+
+    #[test]
+    fn hsrp_v2_group_state() {
+        let mut payload = vec![0x01, 40, 0x02, 0x00, 0x06, 0x04, 0x00, 0x0a];
+        payload.extend([0x00, 0x1b, 0x54, 0x01, 0x02, 0x03]); // identifier
+        payload.extend(150u32.to_be_bytes());
+        payload.extend([0u8; 8]); // hello and hold times
+        payload.extend([10, 0, 10, 1]);
+        payload.extend([0u8; 12]);
+
+        let parsed = parse_hsrp(&payload).unwrap();
+        assert_eq!(parsed.group_id, 10);
+        assert_eq!(parsed.state, "active");
+        assert_eq!(parsed.priority, 150);
+        assert_eq!(parsed.virtual_ip, Some(IpAddr::V4(Ipv4Addr::new(10, 0, 10, 1))));
+        assert_eq!(parsed.virtual_mac, Some([0x00, 0x00, 0x0C, 0x9F, 0xF0, 0x0a]));
+        assert_eq!(parsed.member_mac, Some([0x00, 0x1b, 0x54, 0x01, 0x02, 0x03]));
+    }
+
+    #[test]
+    fn vrrp_v3() {
+        let payload = [0x31, 0x05, 0x64, 0x01, 0x00, 0x64, 0x00, 0x00, 192, 168, 1, 1];
+        let parsed = parse_vrrp(&payload, false).unwrap();
+        assert_eq!(parsed.group_id, 5);
+        assert_eq!(parsed.state, "master");
+        assert_eq!(parsed.virtual_ip, Some(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1))));
+        assert_eq!(parsed.virtual_mac, Some([0x00, 0x00, 0x5E, 0x00, 0x01, 0x05]));
+    }
+
+    #[test]
+    fn glbp_hello_and_forwarder() {
+        let mut payload = vec![0x01, 0x00, 0x00, 0x14, 0x00, 0x00, 0x00, 0x1b, 0x54, 0x0a, 0x0b, 0x0c];
+        let mut hello = vec![0x01, 28, 0x00, 0x20, 0x00, 0x78];
+        hello.extend([0u8; 16]);
+        hello.extend([0x01, 0x04, 10, 0, 20, 1]);
+        payload.extend(hello);
+        let mut forwarder = vec![0x02, 20];
+        forwarder.extend([0u8; 12]);
+        forwarder.extend([0x00, 0x07, 0xb4, 0x01, 0x40, 0x01]);
+        payload.extend(forwarder);
+
+        let parsed = parse_glbp(&payload).unwrap();
+        assert_eq!(parsed.group_id, 20);
+        assert_eq!(parsed.state, "active");
+        assert_eq!(parsed.priority, 120);
+        assert_eq!(parsed.virtual_ip, Some(IpAddr::V4(Ipv4Addr::new(10, 0, 20, 1))));
+        assert_eq!(parsed.virtual_mac, Some([0x00, 0x07, 0xb4, 0x01, 0x40, 0x01]));
+        assert_eq!(parsed.member_mac, Some([0x00, 0x1b, 0x54, 0x0a, 0x0b, 0x0c]));
+    }
+}
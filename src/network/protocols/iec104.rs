@@ -0,0 +1,51 @@
+/// Addressing of the first I-format APDU of an IEC 60870-5-104 segment.
+#[derive(Debug)]
+pub struct Iec104Asdu {
+    pub originator_address: u8,
+    pub common_address: u16,
+}
+
+const START_BYTE: u8 = 0x68;
+
+pub fn parse(payload: &[u8]) -> Option<Iec104Asdu> {
+    let mut pos = 0;
+    // APCI: start, length, control field(4). S- and U-format frames carry no ASDU.
+    while pos + 6 <= payload.len() {
+        if payload[pos] != START_BYTE {
+            return None;
+        }
+        let length = payload[pos + 1] as usize;
+        if payload[pos + 2] & 0x01 == 0 {
+            // ASDU: type id, VSQ, cause of transmission, originator, common address(2, LE)
+            let asdu = payload.get(pos + 6..pos + 12)?;
+            return Some(Iec104Asdu {
+                originator_address: asdu[3],
+                common_address: u16::from_le_bytes([asdu[4], asdu[5]]),
+            });
+        }
+        pos += 2 + length;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn i_format_after_s_format() {
+        let payload = [
+            0x68, 0x04, 0x01, 0x00, 0x02, 0x00, // S-format acknowledgement
+            0x68, 0x0e, 0x00, 0x00, 0x00, 0x00, 0x64, 0x01, 0x06, 0x05, 0xe9, 0x03,
+            0x00, 0x00, 0x00, 0x14,
+        ];
+        let parsed = parse(&payload).unwrap();
+        assert_eq!(parsed.originator_address, 5);
+        assert_eq!(parsed.common_address, 1001);
+    }
+
+    #[test]
+    fn u_format_only() {
+        assert!(parse(&[0x68, 0x04, 0x07, 0x00, 0x00, 0x00]).is_none());
+    }
+}
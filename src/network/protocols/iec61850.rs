@@ -0,0 +1,150 @@
+/// A GOOSE or Sampled Values stream as identified by its control block.
+#[derive(Debug)]
+pub struct Iec61850Stream {
+    pub protocol: &'static str,
+    pub appid: u16,
+    /// gocbRef for GOOSE, svID for Sampled Values.
+    pub control_ref: String,
+    pub dataset: Option<String>,
+    pub conf_rev: u32,
+    /// Only meaningful for Sampled Values.
+    pub smp_cnt: Option<u16>,
+}
+
+pub const ETHER_TYPE_GOOSE: u16 = 0x88B8;
+pub const ETHER_TYPE_SV: u16 = 0x88BA;
+
+/// Parses the frame that follows the GOOSE/SV ether type: APPID, length, reserved(4), APDU.
+pub fn parse(ether_type: u16, payload: &[u8]) -> Option<Iec61850Stream> {
+    if payload.len() < 8 {
+        return None;
+    }
+    let appid = u16::from_be_bytes([payload[0], payload[1]]);
+    let (tag, pdu, _) = ber(&payload[8..])?;
+
+    match (ether_type, tag) {
+        (ETHER_TYPE_GOOSE, 0x61) => {
+            let mut stream = Iec61850Stream {
+                protocol: "GOOSE",
+                appid,
+                control_ref: String::new(),
+                dataset: None,
+                conf_rev: 0,
+                smp_cnt: None,
+            };
+            for (tag, value) in BerIter(pdu) {
+                match tag {
+                    0x80 => stream.control_ref = visible_string(value),
+                    0x82 => stream.dataset = Some(visible_string(value)),
+                    0x88 => stream.conf_rev = unsigned(value),
+                    _ => {}
+                }
+            }
+            (!stream.control_ref.is_empty()).then_some(stream)
+        }
+        (ETHER_TYPE_SV, 0x60) => {
+            // savPdu: noASDU, seqASDU { ASDU { svID, [datSet], smpCnt, confRev, ... } }
+            let (_, seq) = BerIter(pdu).find(|(tag, _)| *tag == 0xA2)?;
+            let (_, asdu) = BerIter(seq).find(|(tag, _)| *tag == 0x30)?;
+
+            let mut stream = Iec61850Stream {
+                protocol: "SV",
+                appid,
+                control_ref: String::new(),
+                dataset: None,
+                conf_rev: 0,
+                smp_cnt: None,
+            };
+            for (tag, value) in BerIter(asdu) {
+                match tag {
+                    0x80 => stream.control_ref = visible_string(value),
+                    0x81 => stream.dataset = Some(visible_string(value)),
+                    0x82 => stream.smp_cnt = Some(unsigned(value) as u16),
+                    0x83 => stream.conf_rev = unsigned(value),
+                    _ => {}
+                }
+            }
+            (!stream.control_ref.is_empty()).then_some(stream)
+        }
+        _ => None,
+    }
+}
+
+fn visible_string(value: &[u8]) -> String {
+    String::from_utf8_lossy(value).trim_end_matches('\0').to_string()
+}
+
+fn unsigned(value: &[u8]) -> u32 {
+    value.iter().take(4).fold(0, |acc, &b| (acc << 8) | u32::from(b))
+}
+
+/// Reads one BER TLV, returning (tag, value, rest).
+fn ber(data: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let tag = *data.first()?;
+    let first = *data.get(1)? as usize;
+    let (len, header) = match first {
+        0x81 => (*data.get(2)? as usize, 3),
+        0x82 => (u16::from_be_bytes([*data.get(2)?, *data.get(3)?]) as usize, 4),
+        n if n < 0x80 => (n, 2),
+        _ => return None,
+    };
+    let value = data.get(header..header + len)?;
+    Some((tag, value, &data[header + len..]))
+}
+
+struct BerIter<'a>(&'a [u8]);
+
+impl<'a> Iterator for BerIter<'a> {
+    type Item = (u8, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (tag, value, rest) = ber(self.0)?;
+        self.0 = rest;
+        Some((tag, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn goose() {
+        let mut pdu = vec![0x80, 0x0f];
+        pdu.extend(b"IED1LD0/LLN0$GO");
+        pdu.extend([0x81, 0x01, 0x00]);
+        pdu.extend([0x82, 0x0b]);
+        pdu.extend(b"IED1LD0/DS1");
+        pdu.extend([0x88, 0x01, 0x03]);
+        let mut payload = vec![0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x61, pdu.len() as u8];
+        payload.extend(pdu);
+
+        let parsed = parse(ETHER_TYPE_GOOSE, &payload).unwrap();
+        assert_eq!(parsed.protocol, "GOOSE");
+        assert_eq!(parsed.appid, 1);
+        assert_eq!(parsed.control_ref, "IED1LD0/LLN0$GO");
+        assert_eq!(parsed.dataset.as_deref(), Some("IED1LD0/DS1"));
+        assert_eq!(parsed.conf_rev, 3);
+    }
+
+    #[test]
+    fn sampled_values() {
+        let mut asdu = vec![0x80, 0x06];
+        asdu.extend(b"MU01SV");
+        asdu.extend([0x82, 0x02, 0x0f, 0x9f, 0x83, 0x04, 0x00, 0x00, 0x00, 0x01]);
+        let mut seq = vec![0x30, asdu.len() as u8];
+        seq.extend(asdu);
+        let mut pdu = vec![0x80, 0x01, 0x01, 0xa2, seq.len() as u8];
+        pdu.extend(seq);
+        let mut payload = vec![0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x60, pdu.len() as u8];
+        payload.extend(pdu);
+
+        let parsed = parse(ETHER_TYPE_SV, &payload).unwrap();
+        assert_eq!(parsed.protocol, "SV");
+        assert_eq!(parsed.appid, 0x4000);
+        assert_eq!(parsed.control_ref, "MU01SV");
+        assert_eq!(parsed.smp_cnt, Some(3999));
+        assert_eq!(parsed.conf_rev, 1);
+        assert!(parse(ETHER_TYPE_GOOSE, &payload).is_none());
+    }
+}
@@ -0,0 +1,76 @@
+/// Device information DIB from a KNXnet/IP search or description response.
+#[derive(Debug)]
+pub struct KnxDevice {
+    pub friendly_name: Option<String>,
+    pub serial_number: String,
+    pub individual_address: String,
+    pub mac: [u8; 6],
+}
+
+const SEARCH_RESPONSE: u16 = 0x0202;
+const DESCRIPTION_RESPONSE: u16 = 0x0204;
+const SEARCH_RESPONSE_EXTENDED: u16 = 0x020C;
+const DIB_DEVICE_INFO: u8 = 0x01;
+
+pub fn parse(payload: &[u8]) -> Option<KnxDevice> {
+    // header length 6, protocol version 0x10, service type(2), total length(2)
+    if payload.len() < 6 || payload[0] != 0x06 || payload[1] != 0x10 {
+        return None;
+    }
+    let service = u16::from_be_bytes([payload[2], payload[3]]);
+    let dib_at = match service {
+        // Search responses carry the control endpoint HPAI (8 bytes) first.
+        SEARCH_RESPONSE | SEARCH_RESPONSE_EXTENDED => 6 + 8,
+        DESCRIPTION_RESPONSE => 6,
+        _ => return None,
+    };
+
+    // length, type, medium, status, individual address(2), project id(2),
+    // serial(6), multicast address(4), MAC(6), friendly name(30)
+    let dib = payload.get(dib_at..dib_at + 54)?;
+    if dib[0] != 0x36 || dib[1] != DIB_DEVICE_INFO {
+        return None;
+    }
+
+    // Friendly name is ISO 8859-1, which maps 1:1 onto the first 256 code points.
+    let name: String = dib[24..54].iter().take_while(|&&b| b != 0).map(|&b| b as char).collect();
+    let mut mac = [0u8; 6];
+    mac.copy_from_slice(&dib[18..24]);
+
+    Some(KnxDevice {
+        friendly_name: Some(name.trim().to_string()).filter(|n| !n.is_empty()),
+        serial_number: dib[8..14].iter().map(|b| format!("{:02X}", b)).collect(),
+        individual_address: format!("{}.{}.{}", dib[4] >> 4, dib[4] & 0x0F, dib[5]),
+        mac,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_response() {
+        let mut payload = vec![0x06, 0x10, 0x02, 0x02, 0x00, 0x4c];
+        payload.extend([0x08, 0x01, 192, 168, 1, 30, 0x0e, 0x57]); // control endpoint
+        payload.extend([0x36, 0x01, 0x02, 0x00, 0x11, 0x05, 0x00, 0x00]);
+        payload.extend([0x00, 0xc5, 0x01, 0x02, 0x03, 0x04]); // serial
+        payload.extend([224, 0, 23, 12]);
+        payload.extend([0x00, 0x24, 0x6d, 0x01, 0x02, 0x03]);
+        let mut name = b"IP Router \xe9tage 1".to_vec();
+        name.resize(30, 0);
+        payload.extend(name);
+
+        let parsed = parse(&payload).unwrap();
+        assert_eq!(parsed.individual_address, "1.1.5");
+        assert_eq!(parsed.serial_number, "00C501020304");
+        assert_eq!(parsed.mac, [0x00, 0x24, 0x6d, 0x01, 0x02, 0x03]);
+        assert_eq!(parsed.friendly_name.as_deref(), Some("IP Router étage 1"));
+    }
+
+    #[test]
+    fn search_request() {
+        let payload = [0x06, 0x10, 0x02, 0x01, 0x00, 0x0e, 0x08, 0x01, 192, 168, 1, 2, 0x0e, 0x57];
+        assert!(parse(&payload).is_none());
+    }
+}
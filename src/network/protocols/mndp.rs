@@ -0,0 +1,82 @@
+use std::net::Ipv4Addr;
+
+/// A MikroTik Neighbor Discovery Protocol announcement.
+#[derive(Debug, Default)]
+pub struct MndpAnnouncement {
+    pub mac: Option<String>,
+    pub identity: Option<String>,
+    pub version: Option<String>,
+    pub platform: Option<String>,
+    pub uptime: Option<u32>,
+    pub software_id: Option<String>,
+    pub board: Option<String>,
+    pub interface: Option<String>,
+    pub ipv4: Option<Ipv4Addr>,
+}
+
+/// Parses the UDP 5678 payload: header(2), sequence(2), then big endian type/length TLVs.
+pub fn parse(payload: &[u8]) -> Option<MndpAnnouncement> {
+    let mut announcement = MndpAnnouncement::default();
+    let mut pos = 4;
+    while let Some(header) = payload.get(pos..pos + 4) {
+        let tlv_type = u16::from_be_bytes([header[0], header[1]]);
+        let len = u16::from_be_bytes([header[2], header[3]]) as usize;
+        let Some(value) = payload.get(pos + 4..pos + 4 + len) else { break };
+        match tlv_type {
+            1 if len == 6 => announcement.mac = Some(super::format_mac(value)),
+            5 => announcement.identity = text(value),
+            7 => announcement.version = text(value),
+            8 => announcement.platform = text(value),
+            // Uptime is the one little endian field.
+            10 if len == 4 => announcement.uptime = Some(u32::from_le_bytes([value[0], value[1], value[2], value[3]])),
+            11 => announcement.software_id = text(value),
+            12 => announcement.board = text(value),
+            16 => announcement.interface = text(value),
+            17 if len == 4 => announcement.ipv4 = Some(Ipv4Addr::new(value[0], value[1], value[2], value[3])),
+            _ => {}
+        }
+        pos += 4 + len;
+    }
+
+    // Discovery requests are a bare 4-byte header.
+    announcement.identity.is_some().then_some(announcement)
+}
+
+fn text(value: &[u8]) -> Option<String> {
+    let s = String::from_utf8_lossy(value).trim_end_matches('\0').trim().to_string();
+    (!s.is_empty()).then_some(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A RouterOS 7 announcement: MAC, identity, version, platform, uptime, board, interface.
+    const ANNOUNCEMENT: &[u8] = &[
+        0x00, 0x00, 0x00, 0x2a, // header, sequence
+        0x00, 0x01, 0x00, 0x06, 0x48, 0x8f, 0x5a, 0x01, 0x02, 0x03,
+        0x00, 0x05, 0x00, 0x06, b'c', b'o', b'r', b'e', b'-', b'1',
+        0x00, 0x07, 0x00, 0x06, b'7', b'.', b'1', b'4', b'.', b'2',
+        0x00, 0x08, 0x00, 0x08, b'M', b'i', b'k', b'r', b'o', b'T', b'i', b'k',
+        0x00, 0x0a, 0x00, 0x04, 0x80, 0x51, 0x01, 0x00, // 86400 s, little endian
+        0x00, 0x0c, 0x00, 0x07, b'R', b'B', b'5', b'0', b'0', b'9', 0x00,
+        0x00, 0x10, 0x00, 0x05, b'e', b't', b'h', b'e', b'r',
+    ];
+
+    #[test]
+    fn announcement() {
+        let parsed = parse(ANNOUNCEMENT).unwrap();
+        assert_eq!(parsed.mac.as_deref(), Some("48:8F:5A:01:02:03"));
+        assert_eq!(parsed.identity.as_deref(), Some("core-1"));
+        assert_eq!(parsed.version.as_deref(), Some("7.14.2"));
+        assert_eq!(parsed.platform.as_deref(), Some("MikroTik"));
+        assert_eq!(parsed.uptime, Some(86_400));
+        assert_eq!(parsed.board.as_deref(), Some("RB5009"));
+        assert_eq!(parsed.interface.as_deref(), Some("ether"));
+    }
+
+    #[test]
+    fn discovery_request() {
+        assert!(parse(&[0, 0, 0, 0]).is_none());
+    }
+}
@@ -0,0 +1,30 @@
+pub mod bacnet;
+pub mod browser;
+pub mod coap;
+pub mod dnp3;
+pub mod enip;
+pub mod fhrp;
+pub mod iec104;
+pub mod iec61850;
+pub mod knx;
+pub mod mndp;
+pub mod modbus;
+pub mod mqtt;
+pub mod multicast;
+pub mod netflow;
+pub mod ntp;
+pub mod opcua;
+pub mod profinet;
+pub mod ptp;
+pub mod routing;
+pub mod s7comm;
+pub mod sflow;
+pub mod tunnel;
+pub mod tzsp;
+pub mod ubnt;
+pub mod wifi;
+
+pub fn format_mac(mac: &[u8]) -> String {
+    format!("{:02X}:{:02X}:{:02X}:{:02X}:{:02X}:{:02X}",
+        mac[0], mac[1], mac[2], mac[3], mac[4], mac[5])
+}
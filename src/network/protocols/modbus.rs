@@ -0,0 +1,81 @@
+/// Unit IDs and function codes of the Modbus/TCP ADUs in one TCP segment.
+#[derive(Debug, Default)]
+pub struct ModbusSegment {
+    pub unit_ids: Vec<u8>,
+    pub function_codes: Vec<u8>,
+}
+
+/// Parses the MBAP headers of every ADU in the segment.
+pub fn parse(payload: &[u8]) -> Option<ModbusSegment> {
+    let mut segment = ModbusSegment::default();
+    let mut pos = 0;
+
+    // transaction id(2), protocol id(2) = 0, length(2), unit id, function code
+    while pos + 8 <= payload.len() {
+        let protocol_id = u16::from_be_bytes([payload[pos + 2], payload[pos + 3]]);
+        let length = u16::from_be_bytes([payload[pos + 4], payload[pos + 5]]) as usize;
+        if protocol_id != 0 || !(2..=254).contains(&length) {
+            break;
+        }
+        let unit_id = payload[pos + 6];
+        // Exception responses echo the function code with the high bit set.
+        let function_code = payload[pos + 7] & 0x7F;
+
+        if !segment.unit_ids.contains(&unit_id) {
+            segment.unit_ids.push(unit_id);
+        }
+        if !segment.function_codes.contains(&function_code) {
+            segment.function_codes.push(function_code);
+        }
+        pos += 6 + length;
+    }
+
+    (!segment.function_codes.is_empty()).then_some(segment)
+}
+
+pub fn function_name(code: u8) -> Option<&'static str> {
+    match code {
+        1 => Some("Read Coils"),
+        2 => Some("Read Discrete Inputs"),
+        3 => Some("Read Holding Registers"),
+        4 => Some("Read Input Registers"),
+        5 => Some("Write Single Coil"),
+        6 => Some("Write Single Register"),
+        8 => Some("Diagnostics"),
+        15 => Some("Write Multiple Coils"),
+        16 => Some("Write Multiple Registers"),
+        17 => Some("Report Server ID"),
+        22 => Some("Mask Write Register"),
+        23 => Some("Read/Write Multiple Registers"),
+        43 => Some("Read Device Identification"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pipelined_requests() {
+        let payload = [
+            0x00, 0x01, 0x00, 0x00, 0x00, 0x06, 0x01, 0x03, 0x00, 0x00, 0x00, 0x0a,
+            0x00, 0x02, 0x00, 0x00, 0x00, 0x06, 0x02, 0x06, 0x00, 0x01, 0x00, 0x03,
+        ];
+        let parsed = parse(&payload).unwrap();
+        assert_eq!(parsed.unit_ids, vec![1, 2]);
+        assert_eq!(parsed.function_codes, vec![3, 6]);
+    }
+
+    #[test]
+    fn exception_response() {
+        let payload = [0x00, 0x01, 0x00, 0x00, 0x00, 0x03, 0x01, 0x83, 0x02];
+        assert_eq!(parse(&payload).unwrap().function_codes, vec![3]);
+        assert_eq!(function_name(3), Some("Read Holding Registers"));
+    }
+
+    #[test]
+    fn other_protocol() {
+        assert!(parse(&[0x00, 0x01, 0x00, 0x01, 0x00, 0x06, 0x01, 0x03]).is_none());
+    }
+}
@@ -0,0 +1,137 @@
+/// The identity a client presents in an MQTT CONNECT packet.
+#[derive(Debug)]
+pub struct MqttConnect {
+    /// 3 = MQTT 3.1, 4 = 3.1.1, 5 = 5.0.
+    pub protocol_level: u8,
+    pub client_id: Option<String>,
+    pub username: Option<String>,
+}
+
+const PACKET_CONNECT: u8 = 0x10;
+const FLAG_USERNAME: u8 = 0x80;
+const FLAG_WILL: u8 = 0x04;
+
+/// Parses a CONNECT at the start of a TCP segment: fixed header, protocol name, level, flags,
+/// keep alive, [v5 properties], client ID, [will properties, topic, payload], [username].
+pub fn parse(payload: &[u8]) -> Option<MqttConnect> {
+    if *payload.first()? != PACKET_CONNECT {
+        return None;
+    }
+    let (_, mut pos) = varint(payload, 1)?;
+
+    let (name, next) = string(payload, pos)?;
+    if name != b"MQTT" && name != b"MQIsdp" {
+        return None;
+    }
+    let protocol_level = *payload.get(next)?;
+    let flags = *payload.get(next + 1)?;
+    pos = next + 4;
+    if protocol_level == 5 {
+        let (len, next) = varint(payload, pos)?;
+        pos = next + len;
+    }
+
+    let (client_id, next) = string(payload, pos)?;
+    pos = next;
+    if flags & FLAG_WILL != 0 {
+        if protocol_level == 5 {
+            let (len, next) = varint(payload, pos)?;
+            pos = next + len;
+        }
+        let (_, next) = string(payload, pos)?;
+        let (_, next) = string(payload, next)?;
+        pos = next;
+    }
+    let username = if flags & FLAG_USERNAME != 0 {
+        string(payload, pos).and_then(|(s, _)| text(s))
+    } else {
+        None
+    };
+
+    Some(MqttConnect {
+        protocol_level,
+        client_id: text(client_id),
+        username,
+    })
+}
+
+pub fn protocol_name(level: u8) -> String {
+    match level {
+        3 => "3.1".to_string(),
+        4 => "3.1.1".to_string(),
+        5 => "5.0".to_string(),
+        other => format!("level {}", other),
+    }
+}
+
+/// Variable byte integer (1-4 bytes, 7 bits each); returns (value, next position).
+fn varint(data: &[u8], mut pos: usize) -> Option<(usize, usize)> {
+    let mut value = 0usize;
+    for shift in (0..28).step_by(7) {
+        let byte = *data.get(pos)?;
+        pos += 1;
+        value |= ((byte & 0x7F) as usize) << shift;
+        if byte & 0x80 == 0 {
+            return Some((value, pos));
+        }
+    }
+    None
+}
+
+/// Length-prefixed UTF-8 string; returns (bytes, next position).
+fn string(data: &[u8], pos: usize) -> Option<(&[u8], usize)> {
+    let len = u16::from_be_bytes([*data.get(pos)?, *data.get(pos + 1)?]) as usize;
+    let value = data.get(pos + 2..pos + 2 + len)?;
+    Some((value, pos + 2 + len))
+}
+
+fn text(value: &[u8]) -> Option<String> {
+    let s = String::from_utf8_lossy(value).trim().to_string();
+    (!s.is_empty()).then_some(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn connect_v311_with_will_and_username() {
+        let mut body = vec![0x00, 0x04, b'M', b'Q', b'T', b'T', 0x04, 0xc4, 0x00, 0x3c];
+        body.extend([0x00, 0x06]);
+        body.extend(b"hmi-01");
+        body.extend([0x00, 0x0a]);
+        body.extend(b"hmi/status");
+        body.extend([0x00, 0x07]);
+        body.extend(b"offline");
+        body.extend([0x00, 0x08]);
+        body.extend(b"operator");
+        body.extend([0x00, 0x02, b'p', b'w']);
+        let mut payload = vec![PACKET_CONNECT, body.len() as u8];
+        payload.extend(body);
+
+        let parsed = parse(&payload).unwrap();
+        assert_eq!(protocol_name(parsed.protocol_level), "3.1.1");
+        assert_eq!(parsed.client_id.as_deref(), Some("hmi-01"));
+        assert_eq!(parsed.username.as_deref(), Some("operator"));
+    }
+
+    #[test]
+    fn connect_v5_with_properties() {
+        let mut body = vec![0x00, 0x04, b'M', b'Q', b'T', b'T', 0x05, 0x02, 0x00, 0x3c];
+        body.extend([0x05, 0x11, 0x00, 0x00, 0x0e, 0x10]); // session expiry interval
+        body.extend([0x00, 0x05]);
+        body.extend(b"gw-17");
+        let mut payload = vec![PACKET_CONNECT, body.len() as u8];
+        payload.extend(body);
+
+        let parsed = parse(&payload).unwrap();
+        assert_eq!(parsed.protocol_level, 5);
+        assert_eq!(parsed.client_id.as_deref(), Some("gw-17"));
+        assert_eq!(parsed.username, None);
+    }
+
+    #[test]
+    fn publish_ignored() {
+        assert!(parse(&[0x30, 0x05, 0x00, 0x01, b't', b'x', b'y']).is_none());
+    }
+}
@@ -0,0 +1,162 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// An IGMP or MLD message reduced to what membership tracking needs.
+#[derive(Debug)]
+pub enum MulticastMessage {
+    Query {
+        protocol: &'static str,
+    },
+    Report {
+        protocol: &'static str,
+        joined: Vec<IpAddr>,
+        left: Vec<IpAddr>,
+    },
+}
+
+// IGMPv3/MLDv2 record types; INCLUDE with no sources means the listener has left.
+const MODE_IS_INCLUDE: u8 = 1;
+const CHANGE_TO_INCLUDE: u8 = 3;
+const BLOCK_OLD_SOURCES: u8 = 6;
+
+/// Parses an IGMP message (IPv4 protocol 2): type, max resp, checksum(2), group(4), ...
+pub fn parse_igmp(payload: &[u8]) -> Option<MulticastMessage> {
+    if payload.len() < 8 {
+        return None;
+    }
+    let group = IpAddr::V4(Ipv4Addr::new(payload[4], payload[5], payload[6], payload[7]));
+
+    match payload[0] {
+        0x11 => {
+            let protocol = if payload.len() >= 12 {
+                "IGMPv3"
+            } else if payload[1] == 0 {
+                "IGMPv1"
+            } else {
+                "IGMPv2"
+            };
+            Some(MulticastMessage::Query { protocol })
+        }
+        0x12 => Some(MulticastMessage::Report { protocol: "IGMPv1", joined: vec![group], left: Vec::new() }),
+        0x16 => Some(MulticastMessage::Report { protocol: "IGMPv2", joined: vec![group], left: Vec::new() }),
+        0x17 => Some(MulticastMessage::Report { protocol: "IGMPv2", joined: Vec::new(), left: vec![group] }),
+        // reserved(2), number of records(2), then group records
+        0x22 => {
+            let count = u16::from_be_bytes([payload[6], payload[7]]);
+            let (joined, left) = group_records(&payload[8..], count, 4)?;
+            Some(MulticastMessage::Report { protocol: "IGMPv3", joined, left })
+        }
+        _ => None,
+    }
+}
+
+/// Parses an MLD message from its ICMPv6 type and the bytes that follow the checksum.
+pub fn parse_mld(icmp_type: u8, body: &[u8]) -> Option<MulticastMessage> {
+    // MLDv1: max response delay(2), reserved(2), multicast address(16)
+    let address = || {
+        let bytes: [u8; 16] = body.get(4..20)?.try_into().ok()?;
+        Some(IpAddr::V6(Ipv6Addr::from(bytes)))
+    };
+
+    match icmp_type {
+        130 if body.len() >= 20 => {
+            let protocol = if body.len() >= 24 { "MLDv2" } else { "MLDv1" };
+            Some(MulticastMessage::Query { protocol })
+        }
+        131 => Some(MulticastMessage::Report { protocol: "MLDv1", joined: vec![address()?], left: Vec::new() }),
+        132 => Some(MulticastMessage::Report { protocol: "MLDv1", joined: Vec::new(), left: vec![address()?] }),
+        // reserved(2), number of records(2), then multicast address records
+        143 if body.len() >= 4 => {
+            let count = u16::from_be_bytes([body[2], body[3]]);
+            let (joined, left) = group_records(&body[4..], count, 16)?;
+            Some(MulticastMessage::Report { protocol: "MLDv2", joined, left })
+        }
+        _ => None,
+    }
+}
+
+/// Walks IGMPv3/MLDv2 records: type, aux len, source count(2), group, sources, aux data.
+fn group_records(data: &[u8], count: u16, addr_len: usize) -> Option<(Vec<IpAddr>, Vec<IpAddr>)> {
+    let mut joined = Vec::new();
+    let mut left = Vec::new();
+    let mut pos = 0;
+    for _ in 0..count {
+        let header = data.get(pos..pos + 4)?;
+        let record_type = header[0];
+        let aux_len = header[1] as usize * 4;
+        let sources = u16::from_be_bytes([header[2], header[3]]) as usize;
+        let raw = data.get(pos + 4..pos + 4 + addr_len)?;
+        let group = if addr_len == 4 {
+            IpAddr::V4(Ipv4Addr::new(raw[0], raw[1], raw[2], raw[3]))
+        } else {
+            IpAddr::V6(Ipv6Addr::from(<[u8; 16]>::try_from(raw).ok()?))
+        };
+
+        match record_type {
+            MODE_IS_INCLUDE | CHANGE_TO_INCLUDE if sources == 0 => left.push(group),
+            BLOCK_OLD_SOURCES => {}
+            _ => joined.push(group),
+        }
+        pos += 4 + addr_len + sources * addr_len + aux_len;
+    }
+    Some((joined, left))
+}
+
+/// Well-known groups that say something about the kind of device listening.
+pub fn service_name(group: &IpAddr) -> Option<&'static str> {
+    match group.to_string().as_str() {
+        "224.0.0.251" | "ff02::fb" => Some("mDNS"),
+        "224.0.0.252" | "ff02::1:3" => Some("LLMNR"),
+        "239.255.255.250" | "ff02::c" | "ff05::c" => Some("SSDP"),
+        "239.255.255.253" => Some("SLP"),
+        "224.0.1.1" | "ff02::101" => Some("NTP"),
+        "224.0.1.129" | "224.0.1.130" | "224.0.1.131" | "224.0.1.132" | "224.0.0.107" | "ff0e::181" | "ff02::6b" => Some("PTP"),
+        "224.0.0.5" | "224.0.0.6" | "ff02::5" | "ff02::6" => Some("OSPF"),
+        "224.0.0.9" | "ff02::9" => Some("RIP"),
+        "224.0.0.10" | "ff02::a" => Some("EIGRP"),
+        "224.0.0.18" | "ff02::12" => Some("VRRP"),
+        "224.0.0.102" => Some("HSRPv2"),
+        "224.0.1.60" => Some("HP Device Discovery"),
+        "224.0.0.113" => Some("AllJoyn"),
+        "239.192.152.143" => Some("BitTorrent LSD"),
+        _ => match group {
+            // Source-specific multicast, typical of IPTV set-top boxes.
+            IpAddr::V4(v4) if v4.octets()[0] == 232 => Some("SSM stream"),
+            _ => None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn igmpv3_report() {
+        let payload = [
+            0x22, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02,
+            0x04, 0x00, 0x00, 0x00, 239, 255, 255, 250, // CHANGE_TO_EXCLUDE {}: join
+            0x03, 0x00, 0x00, 0x00, 224, 0, 0, 251, // CHANGE_TO_INCLUDE {}: leave
+        ];
+        let Some(MulticastMessage::Report { protocol, joined, left }) = parse_igmp(&payload) else { panic!() };
+        assert_eq!(protocol, "IGMPv3");
+        assert_eq!(joined, vec![IpAddr::V4(Ipv4Addr::new(239, 255, 255, 250))]);
+        assert_eq!(left, vec![IpAddr::V4(Ipv4Addr::new(224, 0, 0, 251))]);
+        assert_eq!(service_name(&joined[0]), Some("SSDP"));
+    }
+
+    #[test]
+    fn igmp_queries() {
+        let general = [0x11, 0x64, 0x00, 0x00, 0, 0, 0, 0];
+        assert!(matches!(parse_igmp(&general), Some(MulticastMessage::Query { protocol: "IGMPv2" })));
+        let v3 = [0x11, 0x64, 0x00, 0x00, 0, 0, 0, 0, 0x02, 0x7d, 0x00, 0x00];
+        assert!(matches!(parse_igmp(&v3), Some(MulticastMessage::Query { protocol: "IGMPv3" })));
+    }
+
+    #[test]
+    fn mldv2_report() {
+        let mut body = vec![0x00, 0x00, 0x00, 0x01, 0x04, 0x00, 0x00, 0x00];
+        body.extend("ff02::fb".parse::<Ipv6Addr>().unwrap().octets());
+        let Some(MulticastMessage::Report { joined, .. }) = parse_mld(143, &body) else { panic!() };
+        assert_eq!(service_name(&joined[0]), Some("mDNS"));
+    }
+}
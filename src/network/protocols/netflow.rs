@@ -0,0 +1,248 @@
+use super::format_mac;
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// The source side of one exported flow; destinations are not evidence that a host exists.
+#[derive(Debug)]
+pub struct FlowRecord {
+    pub source: IpAddr,
+    pub source_mac: Option<String>,
+    pub vlan_id: Option<u16>,
+    pub bytes: u64,
+    pub packets: u64,
+}
+
+// Information elements shared by NetFlow v9 and IPFIX.
+const OCTET_DELTA_COUNT: u16 = 1;
+const PACKET_DELTA_COUNT: u16 = 2;
+const SOURCE_IPV4_ADDRESS: u16 = 8;
+const SOURCE_IPV6_ADDRESS: u16 = 27;
+const SOURCE_MAC_ADDRESS: u16 = 56;
+const VLAN_ID: u16 = 58;
+
+const VARIABLE_LENGTH: u16 = 0xFFFF;
+
+#[derive(Debug, Clone, Copy)]
+struct Field {
+    id: u16,
+    length: u16,
+}
+
+/// v9/IPFIX data is meaningless without the template it refers to, and templates arrive in
+/// their own packets, so they are kept per (exporter, source ID / observation domain, template).
+#[derive(Debug, Default)]
+pub struct Templates(HashMap<(IpAddr, u32, u16), Vec<Field>>);
+
+/// Decodes a NetFlow v5, v9 or IPFIX (v10) export packet. v9/IPFIX data sets whose template
+/// has not been seen yet are skipped until the exporter resends it.
+pub fn parse(payload: &[u8], exporter: IpAddr, templates: &mut Templates) -> Vec<FlowRecord> {
+    if payload.len() < 4 {
+        return Vec::new();
+    }
+    match u16::from_be_bytes([payload[0], payload[1]]) {
+        5 => parse_v5(payload),
+        9 => parse_sets(payload, 20, exporter, templates, (0, 1)),
+        10 => parse_sets(payload, 16, exporter, templates, (2, 3)),
+        _ => Vec::new(),
+    }
+}
+
+fn parse_v5(payload: &[u8]) -> Vec<FlowRecord> {
+    let count = u16::from_be_bytes([payload[2], payload[3]]) as usize;
+    payload.get(24..).unwrap_or_default()
+        .chunks_exact(48)
+        .take(count)
+        .map(|record| FlowRecord {
+            source: IpAddr::V4(Ipv4Addr::new(record[0], record[1], record[2], record[3])),
+            source_mac: None,
+            vlan_id: None,
+            packets: u32::from_be_bytes([record[16], record[17], record[18], record[19]]) as u64,
+            bytes: u32::from_be_bytes([record[20], record[21], record[22], record[23]]) as u64,
+        })
+        .collect()
+}
+
+/// Walks the flowsets/sets after the header. `template_ids` is the (template, options template)
+/// set ID pair: (0, 1) for v9, (2, 3) for IPFIX. Data sets use IDs from 256 up in both.
+fn parse_sets(
+    payload: &[u8],
+    header_len: usize,
+    exporter: IpAddr,
+    templates: &mut Templates,
+    template_ids: (u16, u16),
+) -> Vec<FlowRecord> {
+    let mut records = Vec::new();
+    let Some(domain) = payload.get(header_len - 4..header_len)
+        .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]])) else { return records };
+    let ipfix = template_ids.0 == 2;
+
+    let mut pos = header_len;
+    while pos + 4 <= payload.len() {
+        let set_id = u16::from_be_bytes([payload[pos], payload[pos + 1]]);
+        let set_len = u16::from_be_bytes([payload[pos + 2], payload[pos + 3]]) as usize;
+        if set_len < 4 || pos + set_len > payload.len() {
+            break;
+        }
+        let body = &payload[pos + 4..pos + set_len];
+        pos += set_len;
+
+        if set_id == template_ids.0 {
+            parse_templates(body, ipfix, |id, fields| {
+                templates.0.insert((exporter, domain, id), fields);
+            });
+        } else if set_id >= 256
+            && let Some(fields) = templates.0.get(&(exporter, domain, set_id)) {
+            parse_data(body, fields, &mut records);
+        }
+    }
+
+    records
+}
+
+fn parse_templates(body: &[u8], ipfix: bool, mut store: impl FnMut(u16, Vec<Field>)) {
+    let mut pos = 0;
+    while pos + 4 <= body.len() {
+        let id = u16::from_be_bytes([body[pos], body[pos + 1]]);
+        let count = u16::from_be_bytes([body[pos + 2], body[pos + 3]]);
+        pos += 4;
+        if id < 256 {
+            // Padding at the end of the set.
+            return;
+        }
+
+        let mut fields = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let Some(spec) = body.get(pos..pos + 4) else { return };
+            let raw_id = u16::from_be_bytes([spec[0], spec[1]]);
+            let length = u16::from_be_bytes([spec[2], spec[3]]);
+            pos += 4;
+            // IPFIX enterprise-specific elements carry a 4-byte enterprise number; none of
+            // them are used here, so they are kept only for their length.
+            let id = if ipfix && raw_id & 0x8000 != 0 {
+                pos += 4;
+                0
+            } else {
+                raw_id
+            };
+            fields.push(Field { id, length });
+        }
+        store(id, fields);
+    }
+}
+
+fn parse_data(body: &[u8], fields: &[Field], records: &mut Vec<FlowRecord>) {
+    let mut pos = 0;
+    'records: while pos < body.len() {
+        let start = pos;
+        let mut source = None;
+        let mut source_mac = None;
+        let mut vlan_id = None;
+        let mut bytes = 0;
+        let mut packets = 0;
+
+        for field in fields {
+            let length = if field.length == VARIABLE_LENGTH {
+                let Some(&short) = body.get(pos) else { break 'records };
+                pos += 1;
+                if short == 255 {
+                    let Some(long) = body.get(pos..pos + 2) else { break 'records };
+                    pos += 2;
+                    u16::from_be_bytes([long[0], long[1]]) as usize
+                } else {
+                    short as usize
+                }
+            } else {
+                field.length as usize
+            };
+            // Whatever is left over is padding to the set boundary.
+            let Some(value) = body.get(pos..pos + length) else { break 'records };
+            pos += length;
+
+            match (field.id, length) {
+                (OCTET_DELTA_COUNT, 1..=8) => bytes = read_uint(value),
+                (PACKET_DELTA_COUNT, 1..=8) => packets = read_uint(value),
+                (SOURCE_IPV4_ADDRESS, 4) => {
+                    source = Some(IpAddr::V4(Ipv4Addr::new(value[0], value[1], value[2], value[3])));
+                }
+                (SOURCE_IPV6_ADDRESS, 16) => {
+                    source = <[u8; 16]>::try_from(value).ok().map(|a| IpAddr::V6(Ipv6Addr::from(a)));
+                }
+                (SOURCE_MAC_ADDRESS, 6) if value != [0; 6] => source_mac = Some(format_mac(value)),
+                (VLAN_ID, 2) => vlan_id = Some(u16::from_be_bytes([value[0], value[1]]) & 0x0FFF),
+                _ => {}
+            }
+        }
+
+        if let Some(source) = source {
+            records.push(FlowRecord { source, source_mac, vlan_id, bytes, packets });
+        }
+        if pos == start {
+            break;
+        }
+    }
+}
+
+fn read_uint(value: &[u8]) -> u64 {
+    value.iter().fold(0, |acc, &b| (acc << 8) | b as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPORTER: IpAddr = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1));
+
+    #[test]
+    fn v5() {
+        let mut payload = vec![0x00, 0x05, 0x00, 0x01];
+        payload.resize(24, 0);
+        let mut record = [0u8; 48];
+        record[0..4].copy_from_slice(&[10, 1, 2, 3]);
+        record[16..20].copy_from_slice(&12u32.to_be_bytes());
+        record[20..24].copy_from_slice(&3400u32.to_be_bytes());
+        payload.extend(record);
+
+        let records = parse(&payload, EXPORTER, &mut Templates::default());
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].source, IpAddr::V4(Ipv4Addr::new(10, 1, 2, 3)));
+        assert_eq!((records[0].packets, records[0].bytes), (12, 3400));
+    }
+
+    /// v9 header with source ID 7, then the given flowsets.
+    fn v9(sets: &[u8]) -> Vec<u8> {
+        let mut payload = vec![0x00, 0x09, 0x00, 0x01];
+        payload.extend([0u8; 12]);
+        payload.extend(7u32.to_be_bytes());
+        payload.extend(sets);
+        payload
+    }
+
+    // Template 256: IPv4 source, source MAC, VLAN, octets.
+    const TEMPLATE: &[u8] = &[
+        0x00, 0x00, 0x00, 0x18, 0x01, 0x00, 0x00, 0x04,
+        0x00, 0x08, 0x00, 0x04, 0x00, 0x38, 0x00, 0x06, 0x00, 0x3a, 0x00, 0x02, 0x00, 0x01, 0x00, 0x04,
+    ];
+    // One record plus two bytes of padding.
+    const DATA: &[u8] = &[
+        0x01, 0x00, 0x00, 0x16,
+        10, 1, 2, 4, 0x00, 0x1b, 0x1b, 0x01, 0x02, 0x03, 0x10, 0x0a, 0x00, 0x00, 0x05, 0xdc, 0x00, 0x00,
+    ];
+
+    #[test]
+    fn v9_data_needs_template() {
+        let mut templates = Templates::default();
+        assert!(parse(&v9(DATA), EXPORTER, &mut templates).is_empty());
+
+        let records = parse(&v9(&[TEMPLATE, DATA].concat()), EXPORTER, &mut templates);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].source, IpAddr::V4(Ipv4Addr::new(10, 1, 2, 4)));
+        assert_eq!(records[0].source_mac.as_deref(), Some("00:1B:1B:01:02:03"));
+        assert_eq!(records[0].vlan_id, Some(10));
+        assert_eq!(records[0].bytes, 1500);
+
+        // Templates are per exporter.
+        let other = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 2));
+        assert!(parse(&v9(DATA), other, &mut templates).is_empty());
+        assert_eq!(parse(&v9(DATA), EXPORTER, &mut templates).len(), 1);
+    }
+}
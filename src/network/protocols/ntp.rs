@@ -0,0 +1,74 @@
+use std::net::Ipv4Addr;
+
+/// The fields of an NTP header that identify who is serving time to whom.
+#[derive(Debug)]
+pub struct NtpPacket {
+    pub version: u8,
+    pub mode: u8,
+    pub stratum: u8,
+    pub reference_id: [u8; 4],
+}
+
+pub const MODE_CLIENT: u8 = 3;
+pub const MODE_SERVER: u8 = 4;
+pub const MODE_BROADCAST: u8 = 5;
+
+/// Parses LI/VN/mode, stratum, poll, precision, root delay(4), root dispersion(4), reference ID(4).
+pub fn parse(payload: &[u8]) -> Option<NtpPacket> {
+    if payload.len() < 48 {
+        return None;
+    }
+    let version = (payload[0] >> 3) & 0x07;
+    if !(1..=4).contains(&version) {
+        return None;
+    }
+    Some(NtpPacket {
+        version,
+        mode: payload[0] & 0x07,
+        stratum: payload[1],
+        reference_id: [payload[12], payload[13], payload[14], payload[15]],
+    })
+}
+
+/// Stratum 0/1 carry an ASCII kiss code or clock source (e.g. `GPS`); higher strata the
+/// upstream server's IPv4 address (or a hash of its IPv6 address).
+pub fn reference_id_text(packet: &NtpPacket) -> String {
+    if packet.stratum <= 1 {
+        String::from_utf8_lossy(&packet.reference_id).trim_end_matches('\0').to_string()
+    } else {
+        Ipv4Addr::from(packet.reference_id).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packet(first: u8, stratum: u8, reference_id: [u8; 4]) -> Vec<u8> {
+        let mut payload = vec![first, stratum, 0x06, 0xe9];
+        payload.extend([0u8; 8]);
+        payload.extend(reference_id);
+        payload.resize(48, 0);
+        payload
+    }
+
+    #[test]
+    fn stratum_one_server() {
+        let parsed = parse(&packet(0x24, 1, *b"GPS\0")).unwrap();
+        assert_eq!((parsed.version, parsed.mode), (4, MODE_SERVER));
+        assert_eq!(reference_id_text(&parsed), "GPS");
+    }
+
+    #[test]
+    fn upstream_address() {
+        let parsed = parse(&packet(0x1c, 3, [10, 0, 0, 1])).unwrap();
+        assert_eq!(parsed.version, 3);
+        assert_eq!(reference_id_text(&parsed), "10.0.0.1");
+    }
+
+    #[test]
+    fn short_or_bad_version() {
+        assert!(parse(&packet(0x3c, 2, [0; 4])).is_none());
+        assert!(parse(&[0x23; 47]).is_none());
+    }
+}
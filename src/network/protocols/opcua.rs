@@ -0,0 +1,211 @@
+/// Server identity taken from an OPC UA FindServers or GetEndpoints response.
+#[derive(Debug)]
+pub struct OpcUaServer {
+    pub application_uri: Option<String>,
+    pub product_uri: Option<String>,
+    pub application_name: Option<String>,
+}
+
+const FIND_SERVERS_RESPONSE: u32 = 425;
+const GET_ENDPOINTS_RESPONSE: u32 = 431;
+/// Inner diagnostics nest recursively; real servers rarely go past one or two levels.
+const MAX_DIAGNOSTIC_DEPTH: usize = 4;
+
+/// Parses an unencrypted, single-chunk "MSG" message. Signed-and-encrypted channels
+/// are unreadable, but discovery normally runs over SecurityPolicy None.
+pub fn parse(payload: &[u8]) -> Option<OpcUaServer> {
+    // message type(3), chunk type, size(4), secure channel id(4), token id(4),
+    // sequence number(4), request id(4), then the encoded body
+    if payload.len() < 24 || &payload[0..3] != b"MSG" {
+        return None;
+    }
+    let mut r = Reader { data: payload, pos: 24 };
+
+    let type_id = r.node_id()?;
+    if type_id != FIND_SERVERS_RESPONSE && type_id != GET_ENDPOINTS_RESPONSE {
+        return None;
+    }
+
+    // ResponseHeader: timestamp, request handle, service result, diagnostics,
+    // string table, additional header
+    r.skip(8 + 4 + 4)?;
+    r.diagnostic_info(0)?;
+    let strings = r.i32()?;
+    for _ in 0..strings.max(0) {
+        r.string()?;
+    }
+    // Additional header: an ExtensionObject with a ByteString (1) or XmlElement (2) body.
+    r.node_id()?;
+    if matches!(r.u8()?, 1 | 2) {
+        let len = r.i32()?;
+        r.skip(len.max(0) as usize)?;
+    }
+
+    if r.i32()? < 1 {
+        return None;
+    }
+    if type_id == GET_ENDPOINTS_RESPONSE {
+        // EndpointDescription starts with the endpoint URL, then the server description.
+        r.string()?;
+    }
+
+    // ApplicationDescription: applicationUri, productUri, applicationName, ...
+    let application_uri = r.string()?;
+    let product_uri = r.string()?;
+    let application_name = r.localized_text()?;
+
+    Some(OpcUaServer { application_uri, product_uri, application_name })
+}
+
+/// Cursor over the OPC UA binary encoding (little endian).
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        let slice = self.data.get(self.pos..self.pos + len)?;
+        self.pos += len;
+        Some(slice)
+    }
+
+    fn skip(&mut self, len: usize) -> Option<()> {
+        self.bytes(len).map(|_| ())
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.bytes(1).map(|b| b[0])
+    }
+
+    fn i32(&mut self) -> Option<i32> {
+        self.bytes(4).map(|b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    /// A null string (length -1) decodes to None.
+    fn string(&mut self) -> Option<Option<String>> {
+        let len = self.i32()?;
+        if len < 0 {
+            return Some(None);
+        }
+        let bytes = self.bytes(len as usize)?;
+        Some(Some(String::from_utf8_lossy(bytes).into_owned()))
+    }
+
+    fn localized_text(&mut self) -> Option<Option<String>> {
+        let mask = self.u8()?;
+        if mask & 0x01 != 0 {
+            self.string()?;
+        }
+        if mask & 0x02 != 0 {
+            return self.string();
+        }
+        Some(None)
+    }
+
+    /// Returns the numeric identifier (0 for non-numeric ids).
+    fn node_id(&mut self) -> Option<u32> {
+        let encoding = self.u8()?;
+        let id = match encoding & 0x3F {
+            0 => u32::from(self.u8()?),
+            1 => {
+                let b = self.bytes(3)?;
+                u32::from(u16::from_le_bytes([b[1], b[2]]))
+            }
+            2 => {
+                let b = self.bytes(6)?;
+                u32::from_le_bytes([b[2], b[3], b[4], b[5]])
+            }
+            3 | 5 => {
+                self.skip(2)?;
+                let len = self.i32()?;
+                self.skip(len.max(0) as usize)?;
+                0
+            }
+            4 => {
+                self.skip(2 + 16)?;
+                0
+            }
+            _ => return None,
+        };
+        if encoding & 0x80 != 0 {
+            self.string()?;
+        }
+        if encoding & 0x40 != 0 {
+            self.skip(4)?;
+        }
+        Some(id)
+    }
+
+    fn diagnostic_info(&mut self, depth: usize) -> Option<()> {
+        if depth >= MAX_DIAGNOSTIC_DEPTH {
+            return None;
+        }
+        let mask = self.u8()?;
+        for bit in [0x01, 0x02, 0x04, 0x08] {
+            if mask & bit != 0 {
+                self.skip(4)?;
+            }
+        }
+        if mask & 0x10 != 0 {
+            self.string()?;
+        }
+        if mask & 0x20 != 0 {
+            self.skip(4)?;
+        }
+        if mask & 0x40 != 0 {
+            self.diagnostic_info(depth + 1)?;
+        }
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string(out: &mut Vec<u8>, s: &str) {
+        out.extend((s.len() as i32).to_le_bytes());
+        out.extend(s.as_bytes());
+    }
+
+    /// A FindServers response with the given diagnostics and additional header encoding.
+    fn response(diagnostics: &[u8], header_encoding: u8) -> Vec<u8> {
+        let mut out = b"MSGF".to_vec();
+        out.extend([0u8; 20]);
+        out.extend([0x01, 0x00, 0xa9, 0x01]); // four-byte node id 425
+        out.extend([0u8; 16]);
+        out.extend(diagnostics);
+        out.extend(0i32.to_le_bytes());
+        out.extend([0x00, 0x00, header_encoding]);
+        if header_encoding != 0 {
+            string(&mut out, "<x/>");
+        }
+        out.extend(1i32.to_le_bytes());
+        string(&mut out, "urn:plant:plc-7");
+        string(&mut out, "urn:vendor:opcua");
+        out.push(0x02);
+        string(&mut out, "Line 7 PLC");
+        out
+    }
+
+    #[test]
+    fn find_servers_response() {
+        for encoding in [0, 1, 2] {
+            let parsed = parse(&response(&[0x00], encoding)).unwrap();
+            assert_eq!(parsed.application_uri.as_deref(), Some("urn:plant:plc-7"));
+            assert_eq!(parsed.product_uri.as_deref(), Some("urn:vendor:opcua"));
+            assert_eq!(parsed.application_name.as_deref(), Some("Line 7 PLC"));
+        }
+    }
+
+    #[test]
+    fn nested_diagnostics() {
+        // Each level: mask with symbolic id and inner diagnostics, then the id.
+        let shallow: Vec<u8> = [[0x41, 0, 0, 0, 0]; 3].concat().into_iter().chain([0x00]).collect();
+        assert!(parse(&response(&shallow, 0)).is_some());
+
+        let deep: Vec<u8> = [[0x41, 0, 0, 0, 0]; 64].concat().into_iter().chain([0x00]).collect();
+        assert!(parse(&response(&deep, 0)).is_none());
+    }
+}
@@ -0,0 +1,104 @@
+use std::net::Ipv4Addr;
+
+/// Identity published by a PROFINET device in DCP Identify responses and Hello requests.
+#[derive(Debug, Default)]
+pub struct DcpIdentity {
+    pub name_of_station: Option<String>,
+    pub type_of_station: Option<String>,
+    pub vendor_id: Option<u16>,
+    pub device_id: Option<u16>,
+    pub roles: Vec<&'static str>,
+    pub ip: Option<Ipv4Addr>,
+}
+
+const FRAME_ID_HELLO: u16 = 0xFEFC;
+const FRAME_ID_IDENTIFY_RESPONSE: u16 = 0xFEFF;
+
+/// Parses a DCP frame, starting at the FrameID right after the 0x8892 ether type.
+pub fn parse_dcp(payload: &[u8]) -> Option<DcpIdentity> {
+    // frame id(2), service id, service type, xid(4), response delay(2), data length(2)
+    if payload.len() < 12 {
+        return None;
+    }
+    let frame_id = u16::from_be_bytes([payload[0], payload[1]]);
+    if frame_id != FRAME_ID_HELLO && frame_id != FRAME_ID_IDENTIFY_RESPONSE {
+        return None;
+    }
+    let data_len = u16::from_be_bytes([payload[10], payload[11]]) as usize;
+    let end = (12 + data_len).min(payload.len());
+
+    let mut identity = DcpIdentity::default();
+    let mut pos = 12;
+    while pos + 4 <= end {
+        let option = payload[pos];
+        let suboption = payload[pos + 1];
+        let block_len = u16::from_be_bytes([payload[pos + 2], payload[pos + 3]]) as usize;
+        let Some(block) = payload.get(pos + 4..pos + 4 + block_len) else { break };
+
+        // Both Hello and Identify response blocks start with a 2-byte BlockInfo.
+        if let Some(data) = block.get(2..) {
+            match (option, suboption) {
+                (0x01, 0x02) if data.len() >= 4 => {
+                    let ip = Ipv4Addr::new(data[0], data[1], data[2], data[3]);
+                    identity.ip = (!ip.is_unspecified()).then_some(ip);
+                }
+                (0x02, 0x01) => identity.type_of_station = dcp_string(data),
+                (0x02, 0x02) => identity.name_of_station = dcp_string(data),
+                (0x02, 0x03) if data.len() >= 4 => {
+                    identity.vendor_id = Some(u16::from_be_bytes([data[0], data[1]]));
+                    identity.device_id = Some(u16::from_be_bytes([data[2], data[3]]));
+                }
+                (0x02, 0x04) if !data.is_empty() => {
+                    let role = data[0];
+                    for (bit, name) in [(0x01, "io-device"), (0x02, "io-controller"), (0x04, "io-multidevice"), (0x08, "pn-supervisor")] {
+                        if role & bit != 0 {
+                            identity.roles.push(name);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // Blocks are padded to an even length.
+        pos += 4 + block_len + (block_len % 2);
+    }
+
+    Some(identity)
+}
+
+fn dcp_string(data: &[u8]) -> Option<String> {
+    let s = String::from_utf8_lossy(data).trim_end_matches('\0').trim().to_string();
+    (!s.is_empty()).then_some(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identify_response() {
+        let mut payload = vec![0xfe, 0xff, 0x05, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x2c];
+        payload.extend([0x02, 0x01, 0x00, 0x0b, 0x00, 0x00]);
+        payload.extend(b"S7-1500\0\0");
+        payload.push(0x00); // padding
+        payload.extend([0x02, 0x02, 0x00, 0x08, 0x00, 0x00]);
+        payload.extend(b"plc-7\0");
+        payload.extend([0x02, 0x03, 0x00, 0x06, 0x00, 0x00, 0x00, 0x2a, 0x01, 0x0e]);
+        payload.extend([0x01, 0x02, 0x00, 0x0e, 0x00, 0x01, 192, 168, 0, 10]);
+        payload.extend([255, 255, 255, 0, 0, 0, 0, 0]);
+
+        let parsed = parse_dcp(&payload).unwrap();
+        assert_eq!(parsed.type_of_station.as_deref(), Some("S7-1500"));
+        assert_eq!(parsed.name_of_station.as_deref(), Some("plc-7"));
+        assert_eq!(parsed.vendor_id, Some(0x002a));
+        assert_eq!(parsed.device_id, Some(0x010e));
+        assert_eq!(parsed.ip, Some(Ipv4Addr::new(192, 168, 0, 10)));
+    }
+
+    #[test]
+    fn identify_request() {
+        let payload = [0xfe, 0xfe, 0x05, 0x00, 0, 0, 0, 1, 0, 1, 0, 4, 0xff, 0xff, 0, 0];
+        assert!(parse_dcp(&payload).is_none());
+    }
+}
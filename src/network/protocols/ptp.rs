@@ -0,0 +1,92 @@
+/// Common PTPv2 header fields plus the Announce body when present.
+#[derive(Debug)]
+pub struct PtpMessage {
+    pub message_type: u8,
+    pub domain: u8,
+    pub clock_identity: [u8; 8],
+    pub announce: Option<PtpAnnounce>,
+}
+
+#[derive(Debug)]
+pub struct PtpAnnounce {
+    pub priority1: u8,
+    pub priority2: u8,
+    pub clock_class: u8,
+    pub grandmaster_identity: [u8; 8],
+    pub steps_removed: u16,
+}
+
+pub const MESSAGE_DELAY_REQ: u8 = 0x1;
+pub const MESSAGE_PDELAY_REQ: u8 = 0x2;
+pub const MESSAGE_ANNOUNCE: u8 = 0xB;
+
+/// Parses PTPv2 over Ethernet (0x88F7) or UDP 319/320; the header is identical.
+pub fn parse(payload: &[u8]) -> Option<PtpMessage> {
+    // transport/type, version, length(2), domain, minorSdoId, flags(2), correction(8),
+    // type specific(4), clock identity(8), port(2), sequence(2), control, log interval
+    if payload.len() < 34 || payload[1] & 0x0F != 2 {
+        return None;
+    }
+    let message_type = payload[0] & 0x0F;
+    let mut clock_identity = [0u8; 8];
+    clock_identity.copy_from_slice(&payload[20..28]);
+
+    // Announce: origin timestamp(10), UTC offset(2), reserved, priority1, clock quality(4),
+    // priority2, grandmaster identity(8), steps removed(2), time source
+    let announce = if message_type == MESSAGE_ANNOUNCE && payload.len() >= 64 {
+        let mut grandmaster_identity = [0u8; 8];
+        grandmaster_identity.copy_from_slice(&payload[53..61]);
+        Some(PtpAnnounce {
+            priority1: payload[47],
+            priority2: payload[52],
+            clock_class: payload[48],
+            grandmaster_identity,
+            steps_removed: u16::from_be_bytes([payload[61], payload[62]]),
+        })
+    } else {
+        None
+    };
+
+    Some(PtpMessage {
+        message_type,
+        domain: payload[4],
+        clock_identity,
+        announce,
+    })
+}
+
+pub fn format_clock_identity(id: &[u8; 8]) -> String {
+    id.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(":")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn announce() {
+        let mut payload = vec![0x0b, 0x02, 0x00, 0x40, 0x18, 0x00, 0x04, 0x08];
+        payload.extend([0u8; 12]); // correction, type specific
+        payload.extend([0x00, 0x1b, 0x19, 0xff, 0xfe, 0x01, 0x02, 0x03, 0x00, 0x01]);
+        payload.extend([0x00, 0x07, 0x05, 0x01]); // sequence, control, log interval
+        payload.extend([0u8; 10]); // origin timestamp
+        payload.extend([0x00, 0x25, 0x00, 0x80, 0x06, 0x21, 0x4e, 0x5d, 0x80]);
+        payload.extend([0x00, 0x1b, 0x19, 0xff, 0xfe, 0x01, 0x02, 0x03, 0x00, 0x00, 0x20]);
+
+        let parsed = parse(&payload).unwrap();
+        assert_eq!(parsed.message_type, MESSAGE_ANNOUNCE);
+        assert_eq!(parsed.domain, 24);
+        assert_eq!(format_clock_identity(&parsed.clock_identity), "00:1B:19:FF:FE:01:02:03");
+        let announce = parsed.announce.unwrap();
+        assert_eq!((announce.priority1, announce.clock_class, announce.priority2), (128, 6, 128));
+        assert_eq!(announce.grandmaster_identity, parsed.clock_identity);
+        assert_eq!(announce.steps_removed, 0);
+    }
+
+    #[test]
+    fn ptpv1_ignored() {
+        let mut payload = vec![0x00, 0x01];
+        payload.resize(44, 0);
+        assert!(parse(&payload).is_none());
+    }
+}
@@ -0,0 +1,253 @@
+use std::net::{IpAddr, Ipv4Addr};
+
+/// Decoded routing protocol hello/OPEN, reduced to who is speaking and whom it knows.
+#[derive(Debug)]
+pub struct RoutingPacket {
+    pub protocol: &'static str,
+    pub router_id: String,
+    /// OSPF area, IS-IS area address, EIGRP or BGP autonomous system.
+    pub area: Option<String>,
+    pub neighbors: Vec<String>,
+    /// Interface address advertised inside the PDU (IS-IS runs without IP).
+    pub interface_ip: Option<IpAddr>,
+}
+
+fn dotted(b: &[u8]) -> String {
+    Ipv4Addr::new(b[0], b[1], b[2], b[3]).to_string()
+}
+
+/// OSPFv2/v3 hellos (IP protocol 89).
+pub fn parse_ospf(payload: &[u8]) -> Option<RoutingPacket> {
+    // version, type, length(2), router id(4), area id(4)
+    if payload.len() < 16 || payload[1] != 1 {
+        return None;
+    }
+    let length = (u16::from_be_bytes([payload[2], payload[3]]) as usize).min(payload.len());
+
+    // OSPFv2: 24-byte header, then mask, intervals, options, priority, dead, DR, BDR.
+    // OSPFv3: 16-byte header, then interface id, priority, options, intervals, DR, BDR.
+    let neighbors_at = match payload[0] {
+        2 => 24 + 20,
+        3 => 16 + 20,
+        _ => return None,
+    };
+
+    let neighbors = payload.get(neighbors_at..length)
+        .unwrap_or_default()
+        .chunks_exact(4)
+        .map(dotted)
+        .collect();
+
+    Some(RoutingPacket {
+        protocol: "OSPF",
+        router_id: dotted(&payload[4..8]),
+        area: Some(dotted(&payload[8..12])),
+        neighbors,
+        interface_ip: None,
+    })
+}
+
+/// EIGRP hellos (IP protocol 88). Hellos carry no router ID, so the caller keys on the source IP.
+pub fn parse_eigrp(payload: &[u8], src_ip: IpAddr) -> Option<RoutingPacket> {
+    // version, opcode, checksum(2), flags(4), seq(4), ack(4), virtual router id(2), AS(2)
+    if payload.len() < 20 || payload[1] != 5 {
+        return None;
+    }
+    let asn = u16::from_be_bytes([payload[18], payload[19]]);
+
+    Some(RoutingPacket {
+        protocol: "EIGRP",
+        router_id: src_ip.to_string(),
+        area: Some(format!("AS {}", asn)),
+        neighbors: Vec::new(),
+        interface_ip: None,
+    })
+}
+
+/// IS-IS hellos, starting at the 802.2 LLC header (DSAP/SSAP 0xFE).
+pub fn parse_isis(payload: &[u8]) -> Option<RoutingPacket> {
+    if payload.len() < 3 || payload[0..3] != [0xFE, 0xFE, 0x03] {
+        return None;
+    }
+    let pdu = &payload[3..];
+    // discriminator, header length, version, id length, pdu type, version, reserved, max areas
+    if pdu.len() < 8 || pdu[0] != 0x83 {
+        return None;
+    }
+    let header_len = match pdu[4] & 0x1F {
+        15 | 16 => 27, // LAN IIH
+        17 => 20,      // point-to-point IIH
+        _ => return None,
+    };
+    if pdu.len() < header_len {
+        return None;
+    }
+    let system_id = &pdu[9..15];
+    let pdu_len = (u16::from_be_bytes([pdu[17], pdu[18]]) as usize).min(pdu.len());
+
+    let mut packet = RoutingPacket {
+        protocol: "IS-IS",
+        router_id: format!("{:02x}{:02x}.{:02x}{:02x}.{:02x}{:02x}",
+            system_id[0], system_id[1], system_id[2], system_id[3], system_id[4], system_id[5]),
+        area: None,
+        neighbors: Vec::new(),
+        interface_ip: None,
+    };
+
+    let mut pos = header_len;
+    while pos + 2 <= pdu_len {
+        let tlv_type = pdu[pos];
+        let tlv_len = pdu[pos + 1] as usize;
+        let Some(value) = pdu.get(pos + 2..pos + 2 + tlv_len) else { break };
+
+        match tlv_type {
+            // Area addresses: length-prefixed, we keep the first one.
+            1 if packet.area.is_none() => {
+                if let Some(&len) = value.first()
+                    && let Some(area) = value.get(1..1 + len as usize) {
+                    packet.area = Some(area.iter().map(|b| format!("{:02x}", b)).collect::<String>());
+                }
+            }
+            // IS neighbors on a LAN are listed by SNPA (MAC).
+            6 => {
+                packet.neighbors.extend(value.chunks_exact(6).map(super::format_mac));
+            }
+            132 if packet.interface_ip.is_none() && value.len() >= 4 => {
+                packet.interface_ip = Some(IpAddr::V4(Ipv4Addr::new(value[0], value[1], value[2], value[3])));
+            }
+            _ => {}
+        }
+        pos += 2 + tlv_len;
+    }
+
+    Some(packet)
+}
+
+/// RIP responses on UDP 520. Requests may come from any host, so only responses count.
+pub fn parse_rip(payload: &[u8], src_ip: IpAddr) -> Option<RoutingPacket> {
+    if payload.len() < 4 || payload[0] != 2 || !(1..=2).contains(&payload[1]) {
+        return None;
+    }
+
+    Some(RoutingPacket {
+        protocol: "RIP",
+        router_id: src_ip.to_string(),
+        area: Some(format!("RIPv{}", payload[1])),
+        neighbors: Vec::new(),
+        interface_ip: None,
+    })
+}
+
+/// BGP OPEN messages on TCP 179. The peer we are talking to is the only neighbor we know.
+pub fn parse_bgp_open(payload: &[u8], dst_ip: IpAddr) -> Option<RoutingPacket> {
+    let mut pos = 0;
+    // A segment may carry several messages; the OPEN is usually first.
+    while pos + 19 <= payload.len() {
+        let msg = &payload[pos..];
+        if msg[..16].iter().any(|&b| b != 0xFF) {
+            return None;
+        }
+        let length = u16::from_be_bytes([msg[16], msg[17]]) as usize;
+        if length < 19 {
+            return None;
+        }
+
+        // type, version, my AS(2), hold time(2), identifier(4), opt param len
+        if msg[18] == 1 && msg.len() >= 29 {
+            let mut asn = u32::from(u16::from_be_bytes([msg[20], msg[21]]));
+            let opt_len = msg[28] as usize;
+            let opts = msg.get(29..29 + opt_len).unwrap_or_default();
+
+            // Capability 65 carries the real 4-octet AS when AS_TRANS (23456) is announced.
+            let mut p = 0;
+            while p + 2 <= opts.len() {
+                let (param_type, param_len) = (opts[p], opts[p + 1] as usize);
+                let Some(param) = opts.get(p + 2..p + 2 + param_len) else { break };
+                if param_type == 2 {
+                    let mut c = 0;
+                    while c + 2 <= param.len() {
+                        let (code, cap_len) = (param[c], param[c + 1] as usize);
+                        if code == 65 && cap_len == 4
+                            && let Some(v) = param.get(c + 2..c + 6) {
+                            asn = u32::from_be_bytes([v[0], v[1], v[2], v[3]]);
+                        }
+                        c += 2 + cap_len;
+                    }
+                }
+                p += 2 + param_len;
+            }
+
+            return Some(RoutingPacket {
+                protocol: "BGP",
+                router_id: dotted(&msg[24..28]),
+                area: Some(format!("AS {}", asn)),
+                neighbors: vec![dst_ip.to_string()],
+                interface_ip: None,
+            });
+        }
+        pos += length;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ospfv2_hello() {
+        let mut payload = vec![0x02, 0x01, 0x00, 0x30, 1, 1, 1, 1, 0, 0, 0, 0];
+        payload.extend([0u8; 12]); // checksum, auth type, authentication
+        payload.extend([255, 255, 255, 0, 0x00, 0x0a, 0x02, 0x01, 0x00, 0x00, 0x00, 0x28]);
+        payload.extend([10, 0, 0, 1, 0, 0, 0, 0]); // DR, BDR
+        payload.extend([2, 2, 2, 2]);
+
+        let parsed = parse_ospf(&payload).unwrap();
+        assert_eq!(parsed.router_id, "1.1.1.1");
+        assert_eq!(parsed.area.as_deref(), Some("0.0.0.0"));
+        assert_eq!(parsed.neighbors, vec!["2.2.2.2"]);
+    }
+
+    #[test]
+    fn isis_lan_hello() {
+        let mut payload = vec![0xfe, 0xfe, 0x03, 0x83, 0x1b, 0x01, 0x00, 0x0f, 0x01, 0x00, 0x00];
+        payload.push(0x01); // circuit type
+        payload.extend([0x19, 0x21, 0x68, 0x00, 0x10, 0x01]); // system id
+        payload.extend([0x00, 0x1e, 0x00, 0x2f, 0x40]); // holding time, pdu length, priority
+        payload.extend([0x19, 0x21, 0x68, 0x00, 0x10, 0x01, 0x01]); // LAN id
+        payload.extend([0x01, 0x04, 0x03, 0x49, 0x00, 0x01]);
+        payload.extend([0x06, 0x06, 0x00, 0x1b, 0x54, 0x01, 0x02, 0x03]);
+        payload.extend([0x84, 0x04, 10, 0, 0, 2]);
+
+        let parsed = parse_isis(&payload).unwrap();
+        assert_eq!(parsed.router_id, "1921.6800.1001");
+        assert_eq!(parsed.area.as_deref(), Some("490001"));
+        assert_eq!(parsed.neighbors, vec!["00:1B:54:01:02:03"]);
+        assert_eq!(parsed.interface_ip, Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2))));
+    }
+
+    #[test]
+    fn bgp_open_four_octet_as() {
+        let mut payload = vec![0xff; 16];
+        payload.extend([0x00, 0x25, 0x01, 0x04, 0x5b, 0xa0, 0x00, 0xb4, 10, 0, 0, 1, 0x08]);
+        payload.extend([0x02, 0x06, 0x41, 0x04, 0x00, 0x03, 0x0d, 0x41]);
+
+        let peer = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2));
+        let parsed = parse_bgp_open(&payload, peer).unwrap();
+        assert_eq!(parsed.router_id, "10.0.0.1");
+        assert_eq!(parsed.area.as_deref(), Some("AS 200001"));
+        assert_eq!(parsed.neighbors, vec!["10.0.0.2"]);
+    }
+
+    #[test]
+    fn eigrp_and_rip() {
+        let source = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 3));
+        let mut eigrp = vec![0x02, 0x05];
+        eigrp.extend([0u8; 16]);
+        eigrp.extend(100u16.to_be_bytes());
+        assert_eq!(parse_eigrp(&eigrp, source).unwrap().area.as_deref(), Some("AS 100"));
+
+        assert_eq!(parse_rip(&[0x02, 0x02, 0x00, 0x00], source).unwrap().area.as_deref(), Some("RIPv2"));
+        assert!(parse_rip(&[0x01, 0x02, 0x00, 0x00], source).is_none());
+    }
+}
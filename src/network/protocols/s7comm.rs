@@ -0,0 +1,126 @@
+/// What a single S7comm PDU (TPKT/COTP on TCP 102) tells us about its endpoints.
+#[derive(Debug, Default)]
+pub struct S7Pdu {
+    /// Job function for ROSCTR 1 (e.g. read/write variable, PLC stop).
+    pub function: Option<&'static str>,
+    /// Module/CPU identification found in SZL read responses, as (attribute, value).
+    pub identification: Vec<(&'static str, String)>,
+}
+
+const PROTOCOL_ID: u8 = 0x32;
+const COTP_DATA: u8 = 0xF0;
+
+pub fn parse(payload: &[u8]) -> Option<S7Pdu> {
+    // TPKT: version 3, reserved, length(2); COTP: length indicator, PDU type
+    if payload.len() < 7 || payload[0] != 0x03 || payload[5] != COTP_DATA {
+        return None;
+    }
+    let s7 = payload.get(5 + payload[4] as usize..)?;
+
+    // protocol id, ROSCTR, reserved(2), PDU ref(2), param length(2), data length(2), [error(2)]
+    if s7.len() < 10 || s7[0] != PROTOCOL_ID {
+        return None;
+    }
+    let rosctr = s7[1];
+    let param_len = u16::from_be_bytes([s7[6], s7[7]]) as usize;
+    let data_len = u16::from_be_bytes([s7[8], s7[9]]) as usize;
+    let header_len = if rosctr == 2 || rosctr == 3 { 12 } else { 10 };
+    let param = s7.get(header_len..header_len + param_len)?;
+    let data = s7.get(header_len + param_len..header_len + param_len + data_len).unwrap_or_default();
+
+    let mut pdu = S7Pdu::default();
+    match rosctr {
+        1 => pdu.function = param.first().and_then(|&f| job_function(f)),
+        7 => pdu.identification = parse_szl_response(param, data),
+        _ => {}
+    }
+    Some(pdu)
+}
+
+fn job_function(code: u8) -> Option<&'static str> {
+    match code {
+        0x04 => Some("Read Var"),
+        0x05 => Some("Write Var"),
+        0x1A..=0x1C => Some("Download"),
+        0x1D..=0x1F => Some("Upload"),
+        0x28 => Some("PLC Control"),
+        0x29 => Some("PLC Stop"),
+        0xF0 => Some("Setup Communication"),
+        _ => None,
+    }
+}
+
+/// Userdata parameter: head(3), length, method, type/group, subfunction, sequence, ...
+/// We want type 8 (response), group 4 (CPU functions), subfunction 1 (read SZL).
+fn parse_szl_response(param: &[u8], data: &[u8]) -> Vec<(&'static str, String)> {
+    let mut identification = Vec::new();
+    if param.len() < 8 || param[5] != 0x84 || param[6] != 0x01 {
+        return identification;
+    }
+    // return code, transport size, length(2), SZL id(2), index(2), entry length(2), count(2)
+    if data.len() < 12 || data[0] != 0xFF {
+        return identification;
+    }
+    let szl_id = u16::from_be_bytes([data[4], data[5]]) & 0x00FF;
+    let entry_len = u16::from_be_bytes([data[8], data[9]]) as usize;
+    if entry_len < 2 {
+        return identification;
+    }
+
+    for entry in data[12..].chunks_exact(entry_len) {
+        let index = u16::from_be_bytes([entry[0], entry[1]]);
+        let text = || {
+            let end = if szl_id == 0x11 { 22.min(entry.len()) } else { entry.len() };
+            let s = String::from_utf8_lossy(&entry[2..end]).trim_matches(|c: char| c == '\0' || c.is_whitespace()).to_string();
+            (!s.is_empty()).then_some(s)
+        };
+        let key = match (szl_id, index) {
+            (0x11, 0x0001) => "s7.order_number",
+            (0x1C, 0x0001) => "s7.system_name",
+            (0x1C, 0x0002) => "s7.module_name",
+            (0x1C, 0x0005) => "s7.serial_number",
+            (0x1C, 0x0007) => "s7.module_type",
+            _ => continue,
+        };
+        if let Some(value) = text() {
+            identification.push((key, value));
+        }
+    }
+    identification
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn job_request() {
+        let payload = [
+            0x03, 0x00, 0x00, 0x1f, 0x02, 0xf0, 0x80, // TPKT, COTP DT
+            0x32, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x0e, 0x00, 0x00, // job header
+            0x04, 0x01, 0x12, 0x0a, 0x10, 0x02, 0x00, 0x01, 0x00, 0x01, 0x84, 0x00, 0x00, 0x00,
+        ];
+        assert_eq!(parse(&payload).unwrap().function, Some("Read Var"));
+    }
+
+    #[test]
+    fn szl_module_identification() {
+        let mut entry = vec![0x00, 0x01];
+        entry.extend(b"6ES7 516-3AN01-0AB0 ");
+        entry.extend([0x00, 0xc0, 0x00, 0x02, 0x00, 0x01]);
+        let mut data = vec![0xff, 0x09, 0x00, 0x24, 0x00, 0x11, 0x00, 0x00, 0x00, 0x1c, 0x00, 0x01];
+        data.extend(entry);
+
+        let param = [0x00, 0x01, 0x12, 0x08, 0x12, 0x84, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00];
+        let mut s7 = vec![0x32, 0x07, 0x00, 0x00, 0x00, 0x01];
+        s7.extend((param.len() as u16).to_be_bytes());
+        s7.extend((data.len() as u16).to_be_bytes());
+        s7.extend(param);
+        s7.extend(data);
+        let mut payload = vec![0x03, 0x00, 0x00, 0x00, 0x02, 0xf0, 0x80];
+        payload.extend(s7);
+
+        let parsed = parse(&payload).unwrap();
+        assert_eq!(parsed.identification, vec![("s7.order_number", "6ES7 516-3AN01-0AB0".to_string())]);
+    }
+}
@@ -0,0 +1,166 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// The sampled packet headers in an sFlow v5 datagram and the agent that exported them.
+#[derive(Debug)]
+pub struct SflowDatagram<'a> {
+    pub agent: IpAddr,
+    /// Ethernet frames, truncated to the exporter's header length.
+    pub headers: Vec<&'a [u8]>,
+}
+
+const FLOW_SAMPLE: u32 = 1;
+const EXPANDED_FLOW_SAMPLE: u32 = 3;
+const RAW_PACKET_HEADER: u32 = 1;
+const HEADER_PROTOCOL_ETHERNET: u32 = 1;
+
+/// Parses version, agent address, sub agent, sequence, uptime and the sample list, keeping
+/// the raw Ethernet headers of (expanded) flow samples. Counter samples are skipped.
+pub fn parse(payload: &[u8]) -> Option<SflowDatagram<'_>> {
+    let mut reader = Reader { data: payload, pos: 0 };
+    if reader.u32()? != 5 {
+        return None;
+    }
+    let agent = match reader.u32()? {
+        1 => IpAddr::V4(Ipv4Addr::from(<[u8; 4]>::try_from(reader.bytes(4)?).ok()?)),
+        2 => IpAddr::V6(Ipv6Addr::from(<[u8; 16]>::try_from(reader.bytes(16)?).ok()?)),
+        _ => return None,
+    };
+    reader.bytes(12)?;
+    let samples = reader.u32()?;
+
+    // A truncated or malformed sample ends the walk, keeping the headers read so far.
+    let mut headers = Vec::new();
+    for _ in 0..samples {
+        let Some(format) = reader.u32() else { break };
+        let Some(length) = reader.u32() else { break };
+        let Some(sample) = reader.bytes(length as usize) else { break };
+        parse_sample(format, sample, &mut headers);
+    }
+
+    Some(SflowDatagram { agent, headers })
+}
+
+/// Collects the Ethernet headers of one (expanded) flow sample. Returns `None` where the
+/// sample turns out to be malformed; records before that point are kept.
+fn parse_sample<'a>(format: u32, data: &'a [u8], headers: &mut Vec<&'a [u8]>) -> Option<()> {
+    // Enterprise 0 only: sequence, source id, rate, pool, drops, input, output (compact)
+    // or sequence, source type/index, rate, pool, drops, input/output format+value (expanded).
+    let fixed = match format {
+        FLOW_SAMPLE => 28,
+        EXPANDED_FLOW_SAMPLE => 40,
+        _ => return Some(()),
+    };
+    let mut sample = Reader { data, pos: 0 };
+    sample.bytes(fixed)?;
+    let records = sample.u32()?;
+    for _ in 0..records {
+        let record_format = sample.u32()?;
+        let record_length = sample.u32()? as usize;
+        let record = sample.bytes(record_length)?;
+        if record_format == RAW_PACKET_HEADER
+            && let Some(header) = parse_raw_header(record) {
+            headers.push(header);
+        }
+    }
+    Some(())
+}
+
+/// A raw packet header record: protocol, frame length, stripped, header length, header.
+fn parse_raw_header(data: &[u8]) -> Option<&[u8]> {
+    let mut record = Reader { data, pos: 0 };
+    let protocol = record.u32()?;
+    record.bytes(8)?;
+    let header_length = record.u32()? as usize;
+    if protocol != HEADER_PROTOCOL_ETHERNET {
+        return None;
+    }
+    record.bytes(header_length)
+}
+
+/// XDR reader: big endian words, opaque data padded to 4 bytes.
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn u32(&mut self) -> Option<u32> {
+        let b = self.bytes(4)?;
+        Some(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        let value = self.data.get(self.pos..self.pos + len)?;
+        self.pos += len.div_ceil(4) * 4;
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An Ethernet/IPv4 header as carried in a raw packet header record.
+    const FRAME: [u8; 14] = [
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x08, 0x00,
+    ];
+
+    fn datagram(samples: &[(u32, Vec<u8>)]) -> Vec<u8> {
+        let mut data = Vec::new();
+        for word in [5u32, 1] {
+            data.extend(word.to_be_bytes());
+        }
+        data.extend([192, 0, 2, 1]);
+        data.extend([0u8; 12]);
+        data.extend((samples.len() as u32).to_be_bytes());
+        for (format, body) in samples {
+            data.extend(format.to_be_bytes());
+            data.extend((body.len() as u32).to_be_bytes());
+            data.extend(body);
+        }
+        data
+    }
+
+    fn sample(fixed: usize, header: &[u8]) -> Vec<u8> {
+        let mut record = Vec::new();
+        for word in [HEADER_PROTOCOL_ETHERNET, 64, 4, header.len() as u32] {
+            record.extend(word.to_be_bytes());
+        }
+        record.extend(header);
+        record.resize(record.len().div_ceil(4) * 4, 0);
+
+        let mut body = vec![0u8; fixed];
+        body.extend(1u32.to_be_bytes());
+        body.extend(RAW_PACKET_HEADER.to_be_bytes());
+        body.extend((record.len() as u32).to_be_bytes());
+        body.extend(record);
+        body
+    }
+
+    #[test]
+    fn compact_and_expanded_samples() {
+        let payload = datagram(&[
+            (FLOW_SAMPLE, sample(28, &FRAME)),
+            (EXPANDED_FLOW_SAMPLE, sample(40, &FRAME)),
+        ]);
+        let parsed = parse(&payload).unwrap();
+        assert_eq!(parsed.agent, IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1)));
+        assert_eq!(parsed.headers, vec![&FRAME[..], &FRAME[..]]);
+    }
+
+    #[test]
+    fn malformed_sample_keeps_earlier_headers() {
+        let mut broken = sample(40, &FRAME);
+        broken.truncate(50);
+        let payload =
+            datagram(&[(FLOW_SAMPLE, sample(28, &FRAME)), (EXPANDED_FLOW_SAMPLE, broken)]);
+        assert_eq!(parse(&payload).unwrap().headers, vec![&FRAME[..]]);
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        let mut payload = datagram(&[]);
+        payload[3] = 4;
+        assert!(parse(&payload).is_none());
+    }
+}
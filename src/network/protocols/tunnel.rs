@@ -0,0 +1,112 @@
+// Each parser returns the segment key (VNI or ERSPAN session) and the inner Ethernet frame.
+
+const GENEVE_ETHERNET: u16 = 0x6558;
+const GRE_ERSPAN_II: u16 = 0x88BE;
+const GRE_ERSPAN_III: u16 = 0x22EB;
+const GRE_TRANSPARENT_ETHERNET: u16 = 0x6558;
+
+/// VXLAN (UDP 4789): flags, reserved(3), VNI(3), reserved.
+pub fn parse_vxlan(payload: &[u8]) -> Option<(String, &[u8])> {
+    if payload.len() < 8 || payload[0] & 0x08 == 0 {
+        return None;
+    }
+    let vni = u32::from_be_bytes([0, payload[4], payload[5], payload[6]]);
+    Some((format!("vxlan:{}", vni), &payload[8..]))
+}
+
+/// GENEVE (UDP 6081): version/option length, flags, protocol(2), VNI(3), reserved, options.
+pub fn parse_geneve(payload: &[u8]) -> Option<(String, &[u8])> {
+    if payload.len() < 8 || payload[0] >> 6 != 0 {
+        return None;
+    }
+    let protocol = u16::from_be_bytes([payload[2], payload[3]]);
+    if protocol != GENEVE_ETHERNET {
+        return None;
+    }
+    let options_len = (payload[0] & 0x3F) as usize * 4;
+    let vni = u32::from_be_bytes([0, payload[4], payload[5], payload[6]]);
+    Some((format!("geneve:{}", vni), payload.get(8 + options_len..)?))
+}
+
+/// GRE (IP protocol 47) carrying ERSPAN type I/II/III or transparent Ethernet bridging.
+pub fn parse_gre(payload: &[u8]) -> Option<(String, &[u8])> {
+    // flags/version(2), protocol(2), [checksum + reserved(4)], [key(4)], [sequence(4)]
+    if payload.len() < 4 {
+        return None;
+    }
+    let flags = u16::from_be_bytes([payload[0], payload[1]]);
+    let protocol = u16::from_be_bytes([payload[2], payload[3]]);
+    let mut pos = 4;
+    let mut key = None;
+    if flags & 0x8000 != 0 {
+        pos += 4;
+    }
+    if flags & 0x2000 != 0 {
+        let k = payload.get(pos..pos + 4)?;
+        key = Some(u32::from_be_bytes([k[0], k[1], k[2], k[3]]));
+        pos += 4;
+    }
+    let sequenced = flags & 0x1000 != 0;
+    if sequenced {
+        pos += 4;
+    }
+    let body = payload.get(pos..)?;
+
+    match protocol {
+        // Type I has no sequence number and no ERSPAN header.
+        GRE_ERSPAN_II if !sequenced => Some(("erspan".to_string(), body)),
+        // version/VLAN(2), CoS/encap/truncated/session ID(2), reserved/index(4)
+        GRE_ERSPAN_II => {
+            let session = erspan_session(body)?;
+            Some((format!("erspan:{}", session), body.get(8..)?))
+        }
+        // version/VLAN(2), CoS/BSO/T/session ID(2), timestamp(4), SGT(2), flags(2), [subheader(8)]
+        GRE_ERSPAN_III => {
+            let session = erspan_session(body)?;
+            let optional = if body.get(11)? & 0x01 != 0 { 8 } else { 0 };
+            Some((format!("erspan:{}", session), body.get(12 + optional..)?))
+        }
+        GRE_TRANSPARENT_ETHERNET => Some((key.map(|k| format!("gre:{}", k)).unwrap_or_else(|| "gre".to_string()), body)),
+        _ => None,
+    }
+}
+
+fn erspan_session(header: &[u8]) -> Option<u16> {
+    Some(u16::from_be_bytes([*header.get(2)?, *header.get(3)?]) & 0x03FF)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INNER: &[u8] = &[0x00, 0x11, 0x22, 0x33, 0x44, 0x55];
+
+    #[test]
+    fn vxlan() {
+        let payload = [&[0x08, 0x00, 0x00, 0x00, 0x00, 0x13, 0x88, 0x00][..], INNER].concat();
+        assert_eq!(parse_vxlan(&payload), Some(("vxlan:5000".to_string(), INNER)));
+    }
+
+    #[test]
+    fn geneve_with_options() {
+        let mut payload = vec![0x01, 0x00, 0x65, 0x58, 0x00, 0x00, 0x2a, 0x00];
+        payload.extend([0x01, 0x02, 0x03, 0x04]);
+        payload.extend(INNER);
+        assert_eq!(parse_geneve(&payload), Some(("geneve:42".to_string(), INNER)));
+    }
+
+    #[test]
+    fn erspan_ii() {
+        let mut payload = vec![0x10, 0x00, 0x88, 0xbe, 0x00, 0x00, 0x00, 0x01];
+        payload.extend([0x10, 0x00, 0x00, 0x07, 0x00, 0x00, 0x00, 0x00]);
+        payload.extend(INNER);
+        assert_eq!(parse_gre(&payload), Some(("erspan:7".to_string(), INNER)));
+    }
+
+    #[test]
+    fn gre_bridging_with_key() {
+        let mut payload = vec![0x20, 0x00, 0x65, 0x58, 0x00, 0x00, 0x00, 0x09];
+        payload.extend(INNER);
+        assert_eq!(parse_gre(&payload), Some(("gre:9".to_string(), INNER)));
+    }
+}
@@ -0,0 +1,45 @@
+const ENCAPSULATION_ETHERNET: u16 = 1;
+const TAG_PADDING: u8 = 0;
+const TAG_END: u8 = 1;
+
+/// Returns the Ethernet frame carried in a TZSP datagram: version, type, encapsulation(2),
+/// then tagged fields up to TAG_END. Other encapsulations (802.11, Prism) are skipped.
+pub fn parse(payload: &[u8]) -> Option<&[u8]> {
+    if payload.len() < 4 || payload[0] != 1 {
+        return None;
+    }
+    if u16::from_be_bytes([payload[2], payload[3]]) != ENCAPSULATION_ETHERNET {
+        return None;
+    }
+
+    let mut pos = 4;
+    loop {
+        match *payload.get(pos)? {
+            TAG_PADDING => pos += 1,
+            TAG_END => return payload.get(pos + 1..),
+            _ => pos += 2 + *payload.get(pos + 1)? as usize,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ethernet_after_tags() {
+        // version 1, received, Ethernet, padding, RSSI tag, end, then the frame
+        let payload = [0x01, 0x00, 0x00, 0x01, 0x00, 0x0a, 0x01, 0xc4, 0x01, 0xde, 0xad];
+        assert_eq!(parse(&payload), Some(&[0xde, 0xad][..]));
+    }
+
+    #[test]
+    fn skips_other_encapsulations() {
+        assert_eq!(parse(&[0x01, 0x00, 0x00, 0x12, 0x01]), None);
+    }
+
+    #[test]
+    fn unterminated_tags() {
+        assert_eq!(parse(&[0x01, 0x00, 0x00, 0x01, 0x0a, 0x04, 0x00]), None);
+    }
+}
@@ -0,0 +1,86 @@
+use std::net::Ipv4Addr;
+
+/// A Ubiquiti discovery reply (v1 or v2) as sent from UDP 10001.
+#[derive(Debug, Default)]
+pub struct UbntAnnouncement {
+    pub mac: Option<String>,
+    pub ipv4: Option<Ipv4Addr>,
+    pub firmware: Option<String>,
+    pub uptime: Option<u32>,
+    pub hostname: Option<String>,
+    pub platform: Option<String>,
+    pub essid: Option<String>,
+    pub model: Option<String>,
+}
+
+/// Parses version(1), command(1), length(2) and the type(1)/length(2) TLVs that follow.
+pub fn parse(payload: &[u8]) -> Option<UbntAnnouncement> {
+    if payload.len() < 4 || !matches!(payload[0], 1 | 2) {
+        return None;
+    }
+    let declared = u16::from_be_bytes([payload[2], payload[3]]) as usize;
+    let end = (4 + declared).min(payload.len());
+
+    let mut announcement = UbntAnnouncement::default();
+    let mut pos = 4;
+    while pos + 3 <= end {
+        let tlv_type = payload[pos];
+        let len = u16::from_be_bytes([payload[pos + 1], payload[pos + 2]]) as usize;
+        let Some(value) = payload.get(pos + 3..pos + 3 + len) else { break };
+        match tlv_type {
+            0x01 if len == 6 => announcement.mac = Some(super::format_mac(value)),
+            // MAC followed by one of the device's IPv4 addresses; the first one wins.
+            0x02 if len == 10 => {
+                announcement.mac.get_or_insert_with(|| super::format_mac(&value[..6]));
+                announcement.ipv4.get_or_insert(Ipv4Addr::new(value[6], value[7], value[8], value[9]));
+            }
+            0x03 => announcement.firmware = text(value),
+            0x0A if len == 4 => announcement.uptime = Some(u32::from_be_bytes([value[0], value[1], value[2], value[3]])),
+            0x0B => announcement.hostname = text(value),
+            0x0C => announcement.platform = text(value),
+            0x0D => announcement.essid = text(value),
+            0x14 => announcement.model = text(value),
+            _ => {}
+        }
+        pos += 3 + len;
+    }
+
+    // Requests carry no TLVs at all.
+    (announcement.hostname.is_some() || announcement.firmware.is_some()).then_some(announcement)
+}
+
+fn text(value: &[u8]) -> Option<String> {
+    let s = String::from_utf8_lossy(value).trim_end_matches('\0').trim().to_string();
+    (!s.is_empty()).then_some(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A v1 discovery reply: MAC/IP, firmware, uptime, hostname and model.
+    const REPLY: &[u8] = &[
+        0x01, 0x00, 0x00, 0x34, // version, command, length
+        0x02, 0x00, 0x0a, 0x24, 0x5a, 0x4c, 0x01, 0x02, 0x03, 10, 0, 0, 20,
+        0x03, 0x00, 0x0b, b'X', b'M', b'.', b'v', b'6', b'.', b'3', b'.', b'1', b'1', 0x00,
+        0x0a, 0x00, 0x04, 0x00, 0x00, 0x0e, 0x10, // 3600 s, big endian
+        0x0b, 0x00, 0x06, b'a', b'p', b'-', b'l', b'a', b'b',
+        0x14, 0x00, 0x06, b'U', b'6', b'-', b'L', b'R', 0x00,
+    ];
+
+    #[test]
+    fn discovery_reply() {
+        let parsed = parse(REPLY).unwrap();
+        assert_eq!(parsed.mac.as_deref(), Some("24:5A:4C:01:02:03"));
+        assert_eq!(parsed.ipv4, Some(Ipv4Addr::new(10, 0, 0, 20)));
+        assert_eq!(parsed.firmware.as_deref(), Some("XM.v6.3.11"));
+        assert_eq!(parsed.uptime, Some(3600));
+        assert_eq!(parsed.hostname.as_deref(), Some("ap-lab"));
+        assert_eq!(parsed.model.as_deref(), Some("U6-LR"));
+    }
+
+    #[test]
+    fn discovery_request() {
+        assert!(parse(&[0x01, 0x00, 0x00, 0x00]).is_none());
+    }
+}
@@ -0,0 +1,236 @@
+/// What the radiotap header tells us about how a frame was received.
+#[derive(Debug, Default)]
+pub struct Radiotap {
+    pub frequency: Option<u16>,
+    pub signal_dbm: Option<i8>,
+}
+
+/// The management frames used to inventory access points and clients.
+#[derive(Debug)]
+pub struct ManagementFrame {
+    pub kind: FrameKind,
+    pub source: [u8; 6],
+    pub bssid: [u8; 6],
+    pub ssid: Option<String>,
+    pub channel: Option<u8>,
+    /// Only for beacons and probe responses.
+    pub security: Option<&'static str>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FrameKind {
+    Beacon,
+    ProbeRequest,
+    AssociationRequest,
+}
+
+const PRESENT_EXT: u32 = 1 << 31;
+const CAPABILITY_PRIVACY: u16 = 0x0010;
+/// Radiotap flags: the frame ends with its 4-byte FCS.
+const FLAG_FCS: u8 = 0x10;
+
+/// Splits a radiotap header from the 802.11 frame that follows it, without a trailing FCS.
+/// Only the fields up to dBm antenna signal are walked, honouring each field's natural alignment.
+pub fn parse_radiotap(data: &[u8]) -> Option<(Radiotap, &[u8])> {
+    if data.len() < 8 || data[0] != 0 {
+        return None;
+    }
+    let header_len = u16::from_le_bytes([data[2], data[3]]) as usize;
+    let frame = data.get(header_len..)?;
+    let present = u32::from_le_bytes([data[4], data[5], data[6], data[7]]);
+
+    // Fields start after the last chained presence word.
+    let mut pos = 8;
+    let mut word = present;
+    while word & PRESENT_EXT != 0 {
+        let next = data.get(pos..pos + 4)?;
+        word = u32::from_le_bytes([next[0], next[1], next[2], next[3]]);
+        pos += 4;
+    }
+
+    // (bit, alignment, size): TSFT, flags, rate, channel, FHSS, dBm antenna signal
+    let mut info = Radiotap::default();
+    let mut fcs = false;
+    for (bit, align, size) in [(0, 8, 8), (1, 1, 1), (2, 1, 1), (3, 2, 4), (4, 1, 2), (5, 1, 1)] {
+        if present & (1 << bit) == 0 {
+            continue;
+        }
+        pos = pos.div_ceil(align) * align;
+        let field = data.get(pos..pos + size).filter(|_| pos + size <= header_len)?;
+        match bit {
+            1 => fcs = field[0] & FLAG_FCS != 0,
+            3 => info.frequency = Some(u16::from_le_bytes([field[0], field[1]])),
+            5 => info.signal_dbm = Some(field[0] as i8),
+            _ => {}
+        }
+        pos += size;
+    }
+
+    let frame = if fcs { frame.get(..frame.len().checked_sub(4)?)? } else { frame };
+    Some((info, frame))
+}
+
+/// Parses beacons, probe responses, probe requests and (re)association requests.
+pub fn parse_management(frame: &[u8]) -> Option<ManagementFrame> {
+    // frame control(2), duration(2), DA(6), SA(6), BSSID(6), sequence(2)
+    if frame.len() < 24 || (frame[0] >> 2) & 0x03 != 0 {
+        return None;
+    }
+    let (kind, fixed) = match frame[0] >> 4 {
+        0 => (FrameKind::AssociationRequest, 4),
+        2 => (FrameKind::AssociationRequest, 10),
+        4 => (FrameKind::ProbeRequest, 0),
+        5 | 8 => (FrameKind::Beacon, 12),
+        _ => return None,
+    };
+    let source: [u8; 6] = frame[10..16].try_into().ok()?;
+    let bssid: [u8; 6] = frame[16..22].try_into().ok()?;
+    let body = frame.get(24..)?;
+
+    let mut parsed = ManagementFrame {
+        security: None,
+        kind,
+        source,
+        bssid,
+        ssid: None,
+        channel: None,
+    };
+
+    let mut rsn = None;
+    let mut wpa = false;
+    let mut pos = fixed;
+    while let Some(header) = body.get(pos..pos + 2) {
+        let (id, len) = (header[0], header[1] as usize);
+        let Some(value) = body.get(pos + 2..pos + 2 + len) else { break };
+        match id {
+            0 => {
+                let ssid = String::from_utf8_lossy(value).trim_end_matches('\0').to_string();
+                parsed.ssid = (!ssid.is_empty()).then_some(ssid);
+            }
+            3 if len == 1 => parsed.channel = Some(value[0]),
+            48 => rsn = Some(value),
+            221 if value.starts_with(&[0x00, 0x50, 0xF2, 0x01]) => wpa = true,
+            _ => {}
+        }
+        pos += 2 + len;
+    }
+
+    if parsed.kind == FrameKind::Beacon {
+        // timestamp(8), beacon interval(2), capability(2)
+        let capability = u16::from_le_bytes([*body.get(10)?, *body.get(11)?]);
+        parsed.security = Some(match rsn {
+            Some(rsn) => rsn_security(rsn),
+            None if wpa => "WPA",
+            None if capability & CAPABILITY_PRIVACY != 0 => "WEP",
+            None => "Open",
+        });
+    }
+
+    Some(parsed)
+}
+
+/// Names the strongest AKM suite advertised in an RSN element.
+fn rsn_security(rsn: &[u8]) -> &'static str {
+    // version(2), group cipher(4), pairwise count(2), pairwise suites, AKM count(2), AKM suites
+    let akms = || -> Option<Vec<u8>> {
+        let pairwise = u16::from_le_bytes([*rsn.get(6)?, *rsn.get(7)?]) as usize;
+        let pos = 8 + pairwise * 4;
+        let count = u16::from_le_bytes([*rsn.get(pos)?, *rsn.get(pos + 1)?]) as usize;
+        let suites = rsn.get(pos + 2..pos + 2 + count * 4)?;
+        Some(suites.chunks(4).filter(|s| s[..3] == [0x00, 0x0F, 0xAC]).map(|s| s[3]).collect())
+    };
+    let akms = akms().unwrap_or_default();
+
+    if akms.iter().any(|&a| a == 8 || a == 24) {
+        "WPA3-SAE"
+    } else if akms.iter().any(|&a| matches!(a, 1 | 3 | 5 | 11 | 12)) {
+        "WPA2-Enterprise"
+    } else if akms.contains(&18) {
+        "OWE"
+    } else {
+        "WPA2-PSK"
+    }
+}
+
+/// Maps a centre frequency in MHz to its 802.11 channel number.
+pub fn frequency_to_channel(frequency: u16) -> Option<u8> {
+    match frequency {
+        2484 => Some(14),
+        2412..=2472 => Some(((frequency - 2407) / 5) as u8),
+        5955..=7115 => Some(((frequency - 5950) / 5) as u8),
+        5000..=5925 => Some(((frequency - 5000) / 5) as u8),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Beacon for "plant-ops" on channel 6 with an RSN element advertising SAE.
+    const BEACON: &[u8] = &[
+        0x80, 0x00, 0x00, 0x00, // frame control, duration
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, // DA
+        0x02, 0x11, 0x22, 0x33, 0x44, 0x55, // SA
+        0x02, 0x11, 0x22, 0x33, 0x44, 0x55, // BSSID
+        0x10, 0x00, // sequence
+        0, 0, 0, 0, 0, 0, 0, 0, 0x64, 0x00, 0x11, 0x04, // timestamp, interval, capability
+        0x00, 0x09, b'p', b'l', b'a', b'n', b't', b'-', b'o', b'p', b's',
+        0x03, 0x01, 0x06,
+        0x30, 0x14, 0x01, 0x00, 0x00, 0x0f, 0xac, 0x04, 0x01, 0x00, 0x00, 0x0f, 0xac, 0x04,
+        0x01, 0x00, 0x00, 0x0f, 0xac, 0x08, 0x00, 0x00,
+    ];
+
+    /// Radiotap with flags, channel and antenna signal present: 8 + flags(1) + pad(1) +
+    /// channel(4) + signal(1), padded to 16 bytes.
+    fn radiotap(flags: u8) -> Vec<u8> {
+        vec![0x00, 0x00, 0x10, 0x00, 0x2a, 0x00, 0x00, 0x00, flags, 0x00, 0x85, 0x09, 0xa0, 0x00, 0xc4, 0x00]
+    }
+
+    #[test]
+    fn radiotap_fields() {
+        let mut data = radiotap(0);
+        data.extend(BEACON);
+        let (info, frame) = parse_radiotap(&data).unwrap();
+        assert_eq!(info.frequency, Some(2437));
+        assert_eq!(info.signal_dbm, Some(-60));
+        assert_eq!(frame, BEACON);
+    }
+
+    #[test]
+    fn radiotap_trims_fcs() {
+        let mut data = radiotap(FLAG_FCS);
+        data.extend(BEACON);
+        data.extend([0xde, 0xad, 0xbe, 0xef]);
+        let (_, frame) = parse_radiotap(&data).unwrap();
+        assert_eq!(frame, BEACON);
+    }
+
+    #[test]
+    fn beacon() {
+        let parsed = parse_management(BEACON).unwrap();
+        assert_eq!(parsed.kind, FrameKind::Beacon);
+        assert_eq!(parsed.bssid, [0x02, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        assert_eq!(parsed.ssid.as_deref(), Some("plant-ops"));
+        assert_eq!(parsed.channel, Some(6));
+        assert_eq!(parsed.security, Some("WPA3-SAE"));
+    }
+
+    #[test]
+    fn wildcard_probe_request() {
+        let mut frame = BEACON[..24].to_vec();
+        frame[0] = 0x40;
+        frame.extend([0x00, 0x00]);
+        let parsed = parse_management(&frame).unwrap();
+        assert_eq!(parsed.kind, FrameKind::ProbeRequest);
+        assert_eq!(parsed.ssid, None);
+        assert_eq!(parsed.security, None);
+    }
+
+    #[test]
+    fn channels() {
+        assert_eq!(frequency_to_channel(2484), Some(14));
+        assert_eq!(frequency_to_channel(5180), Some(36));
+        assert_eq!(frequency_to_channel(5975), Some(5));
+    }
+}
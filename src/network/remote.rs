@@ -0,0 +1,170 @@
+use crate::domain::models::{Discovery, FlowHost, Observation, CONFIDENCE_FLOW};
+use crate::network::protocols::{netflow, sflow, tzsp};
+use crate::network::sniffer::{RawDiscovery, Sniffer};
+use chrono::Utc;
+use pcap::Linktype;
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::net::{IpAddr, UdpSocket};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tracing::{error, info};
+
+/// Flow records are summed per exporter and source this long before they are handed on,
+/// so a busy router becomes one discovery per host instead of one per flow.
+const FLOW_REPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Remote packet sources: frames arrive over UDP from routers and switches instead of a local
+/// interface, and each discovery is tagged with the exporting agent.
+impl Sniffer {
+    /// TZSP (e.g. MikroTik's packet sniffer streaming) on the given UDP port.
+    pub fn listen_tzsp(self, port: u16, tx: mpsc::Sender<Discovery>) {
+        let Some(socket) = Self::bind(port, "TZSP") else { return };
+        let mut buf = vec![0u8; 65535];
+
+        loop {
+            let (len, from) = match socket.recv_from(&mut buf) {
+                Ok(received) => received,
+                Err(e) => {
+                    error!("TZSP receive error: {}", e);
+                    break;
+                }
+            };
+            let Some(frame) = tzsp::parse(&buf[..len]) else { continue };
+
+            if let Some(mut discovery) = self.process_frame(Linktype::ETHERNET, frame) {
+                // TZSP carries no capture time; arrival is the closest we have.
+                discovery.stamp(Utc::now());
+                self.keep_evidence(&mut discovery, Linktype::ETHERNET, frame, frame.len() as u32);
+                discovery.attributes.push(("agent".to_string(), from.ip().to_string()));
+                if !self.emit(discovery, &tx) {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// sFlow v5 flow samples carrying raw packet headers on the given UDP port.
+    pub fn listen_sflow(self, port: u16, tx: mpsc::Sender<Discovery>) {
+        let Some(socket) = Self::bind(port, "sFlow") else { return };
+        let mut buf = vec![0u8; 65535];
+
+        loop {
+            let len = match socket.recv(&mut buf) {
+                Ok(len) => len,
+                Err(e) => {
+                    error!("sFlow receive error: {}", e);
+                    break;
+                }
+            };
+            let Some(datagram) = sflow::parse(&buf[..len]) else { continue };
+            // Samples only carry the agent's uptime, so they are stamped on arrival.
+            let seen_at = Utc::now();
+
+            // The agent address in the datagram survives NAT and relays, unlike the UDP source.
+            for header in datagram.headers {
+                if let Some(mut discovery) = self.process_frame(Linktype::ETHERNET, header) {
+                    discovery.stamp(seen_at);
+                    self.keep_evidence(&mut discovery, Linktype::ETHERNET, header, header.len() as u32);
+                    discovery.attributes.push(("agent".to_string(), datagram.agent.to_string()));
+                    if !self.emit(discovery, &tx) {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    /// NetFlow v5/v9 and IPFIX export packets on the given UDP port. Only private source
+    /// addresses are kept: public sources are the Internet talking to us, not our hosts.
+    pub fn listen_netflow(self, port: u16, tx: mpsc::Sender<Discovery>) {
+        let Some(socket) = Self::bind(port, "NetFlow/IPFIX") else { return };
+        if let Err(e) = socket.set_read_timeout(Some(Duration::from_secs(1))) {
+            error!("Failed to set NetFlow socket timeout: {}", e);
+            return;
+        }
+        let mut buf = vec![0u8; 65535];
+        let mut templates = netflow::Templates::default();
+        let mut hosts: HashMap<(IpAddr, IpAddr), FlowHost> = HashMap::new();
+        let mut last_report = Instant::now();
+
+        loop {
+            match socket.recv_from(&mut buf) {
+                Ok((len, from)) => {
+                    for record in netflow::parse(&buf[..len], from.ip(), &mut templates) {
+                        if !Self::is_private_addr(&record.source) {
+                            continue;
+                        }
+                        let host = hosts.entry((from.ip(), record.source)).or_insert_with(|| FlowHost {
+                            ip_address: record.source.to_string(),
+                            exporter: from.ip().to_string(),
+                            mac_address: None,
+                            vlan_id: None,
+                            bytes: 0,
+                            packets: 0,
+                        });
+                        host.bytes += record.bytes;
+                        host.packets += record.packets;
+                        host.mac_address = record.source_mac.or(host.mac_address.take());
+                        host.vlan_id = record.vlan_id.or(host.vlan_id);
+                    }
+                }
+                Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+                Err(e) => {
+                    error!("NetFlow receive error: {}", e);
+                    break;
+                }
+            }
+
+            if last_report.elapsed() < FLOW_REPORT_INTERVAL {
+                continue;
+            }
+            last_report = Instant::now();
+            let seen_at = Utc::now();
+
+            // A router exports the MAC it received the flow from, which for hosts behind another
+            // router is that router. A MAC sourcing several IPs is such a hop, not a host.
+            let mut ips_per_mac: HashMap<(IpAddr, String), usize> = HashMap::new();
+            for ((exporter, _), host) in &hosts {
+                if let Some(mac) = &host.mac_address {
+                    *ips_per_mac.entry((*exporter, mac.clone())).or_insert(0) += 1;
+                }
+            }
+
+            for ((exporter, _), mut host) in hosts.drain() {
+                if let Some(mac) = &host.mac_address
+                    && ips_per_mac[&(exporter, mac.clone())] > 1 {
+                    host.mac_address = None;
+                }
+                // Without a trustworthy MAC there is no asset; the host lives in flow_hosts only.
+                let discovery = RawDiscovery {
+                    mac: host.mac_address.clone().unwrap_or_default(),
+                    ip: host.ip_address.clone(),
+                    method: "IPFIX".to_string(),
+                    vlan_id: host.vlan_id.unwrap_or(0),
+                    confidence: Some(CONFIDENCE_FLOW),
+                    attributes: vec![("agent".to_string(), exporter.to_string())],
+                    observations: vec![Observation::FlowHost(host)],
+                    seen_at,
+                    ..Default::default()
+                };
+                if !self.emit(discovery, &tx) {
+                    return;
+                }
+            }
+        }
+    }
+
+    fn bind(port: u16, name: &str) -> Option<UdpSocket> {
+        match UdpSocket::bind(("0.0.0.0", port)) {
+            Ok(socket) => {
+                info!("{} listener active on UDP {}", name, port);
+                Some(socket)
+            }
+            Err(e) => {
+                error!("Failed to bind {} listener on UDP {}: {}", name, port, e);
+                None
+            }
+        }
+    }
+}
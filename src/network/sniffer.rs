@@ -1,24 +1,88 @@
-use pcap::{Capture, Device};
-use etherparse::{PacketHeaders, NetHeaders, LinkHeader, EtherType, TransportHeader, Icmpv6Type};
-use crate::domain::models::Asset;
-use chrono::Utc;
+use pcap::{Capture, Device, Linktype, PacketHeader, Precision};
+use etherparse::{PacketHeaders, NetHeaders, LinkHeader, EtherType, TransportHeader, Icmpv6Type, PayloadSlice};
+use crate::domain::models::{Asset, Discovery, Observation, FhrpHello, RoutingHello, BacnetDevice, Iec61850Publisher, ScadaLink, PtpClock, MulticastMembership, MulticastQuerier, NtpServer, NtpAssociation, MqttSession, WifiDevice, WifiProbe, EvidenceFrame, BOOT_TIME_FORMAT, CONFIDENCE_WIRE};
+use crate::network::protocols::{self, bacnet, browser, coap, dnp3, enip, fhrp, iec104, iec61850, knx, mndp, modbus, mqtt, multicast, ntp, opcua, profinet, ptp, routing, s7comm, tunnel, ubnt, wifi};
+use chrono::{DateTime, Duration, Utc};
 use tracing::{warn, error, info, debug};
 use mac_oui::Oui;
 use tokio::sync::mpsc;
-use std::net::Ipv6Addr;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use dashmap::DashMap;
+
+/// Protocols carried over IP; valid on every link type, including raw IP. Mirror tunnels
+/// (VXLAN, GENEVE, GRE/ERSPAN) pass whole so their inner frames can be filtered in userspace.
+const IP_FILTER: &str = "(udp port 67 or port 68 or port 53 or port 5353 or port 5355 or port 137 or port 138) or \
+    (udp port 1985 or port 2029 or port 3222) or ip proto 112 or ip6 proto 112 or \
+    ip proto 88 or ip proto 89 or ip6 proto 88 or ip6 proto 89 or \
+    udp port 520 or tcp port 179 or \
+    (icmp6 and (ip6[40] == 134 or ip6[40] == 135 or ip6[40] == 136)) or \
+    port 44818 or udp port 47808 or tcp port 502 or tcp port 102 or \
+    port 20000 or tcp port 2404 or udp port 3671 or tcp port 4840 or \
+    udp port 319 or udp port 320 or \
+    udp port 5678 or udp port 10001 or udp port 123 or igmp or \
+    tcp dst port 1883 or udp port 5683 or \
+    (ip6[6] == 0 and ip6[40] == 58 and ip6[41] == 0 and \
+     (ip6[48] == 130 or ip6[48] == 131 or ip6[48] == 132 or ip6[48] == 143)) or \
+    udp port 4789 or udp port 6081 or ip proto 47";
+
+/// Protocols that only exist below IP (Ethernet and Linux cooked captures). PROFINET DCP is
+/// added per link type by `filter_for`, since its FrameID test depends on the header length.
+const L2_FILTER: &str = "arp or isis or \
+    ether proto 0x88b8 or ether proto 0x88ba or ether proto 0x88f7 or \
+    ether proto 0x88cc or ether proto 0x2000";
+
+/// Monitor-mode Wi-Fi only gets the management frames we inventory from.
+const WIFI_FILTER: &str = "type mgt and (subtype beacon or subtype probe-resp or subtype probe-req or \
+    subtype assoc-req or subtype reassoc-req)";
+
+/// Linux reports raw IP as DLT_RAW (12) rather than LINKTYPE_RAW (101).
+const DLT_RAW: Linktype = Linktype(12);
+
+/// An AP beacons about ten times a second; one beacon per BSSID in this window is enough.
+const BEACON_INTERVAL_SECONDS: i64 = 10;
 
 pub struct Sniffer {
-    interface: String,
+    pub(super) interface: String,
     oui_db: Option<Oui>,
+    /// Attach the frame to each discovery so the sync loop can save it.
+    evidence: bool,
+    /// Capture time of the last beacon passed on, per BSSID.
+    beacons: DashMap<String, DateTime<Utc>>,
 }
 
-#[derive(Debug)]
-struct RawDiscovery {
-    mac: String,
-    ip: String,
-    method: String,
-    hostname: Option<String>,
-    vlan_id: u16,
+#[derive(Debug, Default)]
+pub(super) struct RawDiscovery {
+    pub(super) mac: String,
+    pub(super) ip: String,
+    pub(super) method: String,
+    pub(super) hostname: Option<String>,
+    pub(super) model: Option<String>,
+    pub(super) firmware: Option<String>,
+    pub(super) os_guess: Option<String>,
+    pub(super) vlan_id: u16,
+    pub(super) segment: Option<String>,
+    /// `None` for frames seen on the wire (`CONFIDENCE_WIRE`); indirect sources set their own.
+    pub(super) confidence: Option<u8>,
+    /// Device uptime in seconds, turned into a `booted_at` attribute by `stamp`.
+    pub(super) uptime: Option<u32>,
+    pub(super) attributes: Vec<(String, String)>,
+    pub(super) observations: Vec<Observation>,
+    /// Capture time of the packet, filled in by the source (via `stamp`) once the frame is decoded.
+    pub(super) seen_at: DateTime<Utc>,
+    pub(super) evidence: Option<EvidenceFrame>,
+}
+
+impl RawDiscovery {
+    /// Sets the capture time. Uptime is stored as a boot time so it does not change with every
+    /// announcement, and it is taken from the packet's time so replayed captures get it right.
+    /// Readings jitter by a second or so; the database matches them with a tolerance.
+    pub(super) fn stamp(&mut self, seen_at: DateTime<Utc>) {
+        self.seen_at = seen_at;
+        if let Some(uptime) = self.uptime.take() {
+            let booted_at = seen_at - Duration::seconds(i64::from(uptime));
+            self.attributes.push(("booted_at".to_string(), booted_at.format(BOOT_TIME_FORMAT).to_string()));
+        }
+    }
 }
 
 impl Sniffer {
@@ -33,34 +97,72 @@ impl Sniffer {
                 None
             }
         };
-        Sniffer { interface, oui_db }
+        Sniffer { interface, oui_db, evidence: false, beacons: DashMap::new() }
+    }
+
+    /// Keeps the frame behind every discovery, for the evidence files.
+    pub fn with_evidence(mut self, evidence: bool) -> Self {
+        self.evidence = evidence;
+        self
+    }
+
+    /// True for a beacon from a BSSID that already had one passed on within the beacon window.
+    /// Call after `seen_at` is set, before the frame is kept as evidence.
+    pub(super) fn is_repeat_beacon(&self, discovery: &RawDiscovery) -> bool {
+        let Some(bssid) = discovery.observations.iter().find_map(|o| match o {
+            Observation::WifiDevice(device) if device.role == "ap" => Some(&device.bssid),
+            _ => None,
+        }) else { return false };
+
+        match self.beacons.get(bssid) {
+            Some(last) if (discovery.seen_at - *last).num_seconds().abs() < BEACON_INTERVAL_SECONDS => true,
+            _ => {
+                self.beacons.insert(bssid.clone(), discovery.seen_at);
+                false
+            }
+        }
+    }
+
+    /// Copies the frame behind a discovery, when evidence is enabled. Writing it is left to the
+    /// sync loop, which knows whether the frame carried anything new.
+    pub(super) fn keep_evidence(&self, discovery: &mut RawDiscovery, datalink: Linktype, data: &[u8], original_len: u32) {
+        if self.evidence {
+            discovery.evidence = Some(EvidenceFrame { linktype: datalink.0, data: data.to_vec(), original_len });
+        }
     }
 
     fn is_private_ip(ip: [u8; 4]) -> bool {
         match ip {
             [10, _, _, _] => true,
-            [172, b, _, _] if b >= 16 && b <= 31 => true,
+            [172, b, _, _] if (16..=31).contains(&b) => true,
             [192, 168, _, _] => true,
             [169, 254, _, _] => true,
             _ => false,
         }
     }
 
+    /// RFC 1918/link-local IPv4 plus IPv6 unique-local and link-local.
+    pub(super) fn is_private_addr(ip: &IpAddr) -> bool {
+        match ip {
+            IpAddr::V4(v4) => Self::is_private_ip(v4.octets()),
+            IpAddr::V6(v6) => (v6.segments()[0] & 0xfe00) == 0xfc00 || v6.is_unicast_link_local(),
+        }
+    }
+
     fn get_vendor(&self, mac: &str) -> Option<String> {
         let db = self.oui_db.as_ref()?;
         if let Ok(Some(entry)) = db.lookup_by_mac(mac) {
             return Some(entry.company_name.clone());
         }
         let normalized = mac.replace(':', "").to_uppercase();
-        if normalized.len() >= 6 {
-            if let Ok(Some(entry)) = db.lookup_by_mac(&normalized[0..6]) {
-                return Some(entry.company_name.clone());
-            }
+        if normalized.len() >= 6
+            && let Ok(Some(entry)) = db.lookup_by_mac(&normalized[0..6]) {
+            return Some(entry.company_name.clone());
         }
         None
     }
 
-    pub fn start(self, tx: mpsc::Sender<Asset>) {
+    pub fn start(self, tx: mpsc::Sender<Discovery>) {
         let interface_name = self.interface.clone();
         
         let devices = match Device::list() {
@@ -81,6 +183,7 @@ impl Sniffer {
 
         let mut cap = match Capture::from_device(device) {
             Ok(c) => match c.promisc(true)
+                .precision(Precision::Nano)
                 .snaplen(1024)
                 .buffer_size(2 * 1024 * 1024)
                 .immediate_mode(true)
@@ -97,12 +200,8 @@ impl Sniffer {
             }
         };
 
-        let filter = "arp or \
-                      (udp port 67 or port 68 or port 53 or port 5353 or port 5355 or port 137) or \
-                      (icmp6 and (ip6[40] == 134 or ip6[40] == 135 or ip6[40] == 136)) or \
-                      ether proto 0x88cc or ether proto 0x2000";
-
-        if let Err(e) = cap.filter(filter, true) {
+        let datalink = cap.get_datalink();
+        if let Err(e) = cap.filter(&Self::filter_for(datalink), true) {
              warn!("BPF filter error on {}: {}", interface_name, e);
         }
 
@@ -118,35 +217,226 @@ impl Sniffer {
                 }
             };
 
-            if let Some(discovery) = self.process_packet(&packet.data) {
-                debug!("Packet matched discovery rules: {:?}", discovery);
-                
-                let asset = Asset {
-                    mac_address: discovery.mac.clone(),
-                    ip_address: discovery.ip,
-                    hostname: discovery.hostname,
-                    vendor: self.get_vendor(&discovery.mac),
-                    vlan_id: discovery.vlan_id,
-                    discovery_method: discovery.method,
-                    first_seen_at: Utc::now(),
-                    last_seen_at: Utc::now(),
-                };
-                
-                if let Err(e) = tx.try_send(asset) {
-                    match e {
-                        mpsc::error::TrySendError::Full(_) => {
-                            warn!("Buffer full on {}: dropping discovery packet", interface_name);
-                        },
-                        mpsc::error::TrySendError::Closed(_) => break,
-                    }
+            if let Some(mut discovery) = self.process_frame(datalink, packet.data) {
+                discovery.stamp(Self::capture_time(packet.header));
+                if self.is_repeat_beacon(&discovery) {
+                    continue;
+                }
+                self.keep_evidence(&mut discovery, datalink, packet.data, packet.header.len);
+                if !self.emit(discovery, &tx) {
+                    break;
                 }
             }
         }
     }
 
-    fn process_packet(&self, data: &[u8]) -> Option<RawDiscovery> {
+    /// Turns a match into a `Discovery` and queues it for the sync loop. Returns `false` once
+    /// the receiving side has gone away.
+    pub(super) fn emit(&self, discovery: RawDiscovery, tx: &mpsc::Sender<Discovery>) -> bool {
+        match tx.try_send(self.build_discovery(discovery)) {
+            Ok(()) => true,
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                warn!("Buffer full on {}: dropping discovery packet", self.interface);
+                true
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => false,
+        }
+    }
+
+    /// The BPF filter for a link type: 802.11 management frames, IP-only protocols on MAC-less
+    /// links, and the full IP and L2 set everywhere else.
+    pub(super) fn filter_for(datalink: Linktype) -> String {
+        match datalink {
+            Linktype::IEEE802_11_RADIOTAP => WIFI_FILTER.to_string(),
+            DLT_RAW | Linktype::RAW | Linktype::IPV4 | Linktype::IPV6 => IP_FILTER.to_string(),
+            Linktype::LINUX_SLL => format!("{} or {} or {}", IP_FILTER, L2_FILTER, Self::dcp_filter(16)),
+            Linktype::LINUX_SLL2 => format!("{} or {} or {}", IP_FILTER, L2_FILTER, Self::dcp_filter(20)),
+            _ => format!("{} or {} or {}", IP_FILTER, L2_FILTER, Self::dcp_filter(14)),
+        }
+    }
+
+    /// PROFINET DCP frames (FrameID 0xFEFC and up) behind a link header of `header_len` bytes.
+    fn dcp_filter(header_len: usize) -> String {
+        format!("(ether proto 0x8892 and ether[{}:2] >= 0xfefc)", header_len)
+    }
+
+    /// Header timestamp of a capture opened with nanosecond precision (`tv_usec` then holds
+    /// nanoseconds). Linux supports this for live captures and libpcap converts files.
+    pub(super) fn capture_time(header: &PacketHeader) -> DateTime<Utc> {
+        DateTime::from_timestamp(header.ts.tv_sec, header.ts.tv_usec as u32).unwrap_or_else(Utc::now)
+    }
+
+    pub(super) fn build_discovery(&self, discovery: RawDiscovery) -> Discovery {
+        debug!("Packet matched discovery rules: {:?}", discovery);
+
+        // An empty MAC means the link type carried none (raw IP); keep the facts only.
+        let asset = (!discovery.mac.is_empty()).then(|| Asset {
+            mac_address: discovery.mac.clone(),
+            ip_address: discovery.ip,
+            hostname: discovery.hostname,
+            vendor: self.get_vendor(&discovery.mac),
+            model: discovery.model,
+            firmware: discovery.firmware,
+            os_guess: discovery.os_guess,
+            vlan_id: discovery.vlan_id,
+            segment: discovery.segment,
+            confidence: discovery.confidence.unwrap_or(CONFIDENCE_WIRE),
+            discovery_method: discovery.method,
+            first_seen_at: discovery.seen_at,
+            last_seen_at: discovery.seen_at,
+        });
+
+        Discovery {
+            asset,
+            attributes: discovery.attributes,
+            observations: discovery.observations,
+            seen_at: discovery.seen_at,
+            evidence: discovery.evidence,
+        }
+    }
+
+    /// Decodes the link layer for the capture's link type and hands the rest to the common
+    /// discovery path. Without a sender MAC (raw IP) only observations are kept, not assets.
+    pub(super) fn process_frame(&self, datalink: Linktype, data: &[u8]) -> Option<RawDiscovery> {
+        match datalink {
+            Linktype::IEEE802_11_RADIOTAP => self.process_wifi(data),
+            Linktype::LINUX_SLL | Linktype::LINUX_SLL2 => {
+                let (src_mac, ether_type, payload) = Self::parse_sll(datalink, data)?;
+                let value = PacketHeaders::from_ether_type(EtherType(ether_type), payload).ok()?;
+                self.process_headers(&value, src_mac.as_deref().unwrap_or(""), "", None)
+            }
+            DLT_RAW | Linktype::RAW | Linktype::IPV4 | Linktype::IPV6 => {
+                let value = PacketHeaders::from_ip_slice(data).ok()?;
+                self.process_headers(&value, "", "", None)
+            }
+            _ => self.process_ethernet(data, None),
+        }
+    }
+
+    fn process_ethernet(&self, data: &[u8], segment: Option<String>) -> Option<RawDiscovery> {
         let value = PacketHeaders::from_ethernet_slice(data).ok()?;
-        
+        let Some(LinkHeader::Ethernet2(eth)) = &value.link else { return None; };
+        let src_mac = protocols::format_mac(&eth.source);
+        let dst_mac = protocols::format_mac(&eth.destination);
+        self.process_headers(&value, &src_mac, &dst_mac, segment)
+    }
+
+    /// Strips mirror encapsulations (VXLAN, GENEVE, GRE/ERSPAN) and runs discovery on the inner
+    /// frame, tagging it with the tunnel's segment key. Nested tunnels join keys with `/`.
+    fn process_headers(&self, value: &PacketHeaders, src_mac: &str, dst_mac: &str, segment: Option<String>) -> Option<RawDiscovery> {
+        let tunnel = match (&value.transport, &value.payload) {
+            (Some(TransportHeader::Udp(udp)), PayloadSlice::Udp(payload)) => match udp.destination_port {
+                4789 => tunnel::parse_vxlan(payload),
+                6081 => tunnel::parse_geneve(payload),
+                _ => None,
+            },
+            (None, PayloadSlice::Ip(ip_payload)) if ip_payload.ip_number.0 == 47 => tunnel::parse_gre(ip_payload.payload),
+            _ => None,
+        };
+
+        if let Some((key, inner)) = tunnel {
+            let segment = match segment {
+                Some(outer) => format!("{}/{}", outer, key),
+                None => key,
+            };
+            return self.process_ethernet(inner, Some(segment));
+        }
+
+        let mut discovery = self.process_packet(value, src_mac, dst_mac)?;
+        discovery.segment = segment;
+        Some(discovery)
+    }
+
+    /// Linux cooked headers. SLL: packet type(2), ARPHRD(2), address length(2), address(8),
+    /// protocol(2). SLL2: protocol(2), reserved(2), ifindex(4), ARPHRD(2), packet type,
+    /// address length, address(8). Only Ethernet devices give us a usable MAC.
+    fn parse_sll(datalink: Linktype, data: &[u8]) -> Option<(Option<String>, u16, &[u8])> {
+        let (hatype, halen, address, protocol, header_len) = if datalink == Linktype::LINUX_SLL2 {
+            let header = data.get(..20)?;
+            (u16::from_be_bytes([header[8], header[9]]), header[11] as u16, &header[12..20], u16::from_be_bytes([header[0], header[1]]), 20)
+        } else {
+            let header = data.get(..16)?;
+            (u16::from_be_bytes([header[2], header[3]]), u16::from_be_bytes([header[4], header[5]]), &header[6..14], u16::from_be_bytes([header[14], header[15]]), 16)
+        };
+        let mac = (hatype == 1 && halen == 6).then(|| protocols::format_mac(address));
+        Some((mac, protocol, &data[header_len..]))
+    }
+
+    /// 802.11 management frames behind a radiotap header. APs come from beacons and probe
+    /// responses, clients from probe and association requests. Wi-Fi sightings have no VLAN
+    /// and are filed under VLAN 0.
+    fn process_wifi(&self, data: &[u8]) -> Option<RawDiscovery> {
+        let (radiotap, frame) = wifi::parse_radiotap(data)?;
+        let management = wifi::parse_management(frame)?;
+        let mut mac = protocols::format_mac(&management.source);
+        let bssid = protocols::format_mac(&management.bssid);
+        let channel = management.channel.or_else(|| radiotap.frequency.and_then(wifi::frequency_to_channel));
+
+        let mut attributes = Vec::new();
+        let mut observations = Vec::new();
+        match management.kind {
+            wifi::FrameKind::Beacon => {
+                attributes.push(("role".to_string(), "wifi-ap".to_string()));
+                if let Some(ssid) = &management.ssid {
+                    attributes.push(("wifi.ssid".to_string(), ssid.clone()));
+                }
+                observations.push(Observation::WifiDevice(WifiDevice {
+                    mac_address: mac.clone(),
+                    role: "ap".to_string(),
+                    ssid: management.ssid,
+                    bssid,
+                    channel,
+                    security: management.security.map(|s| s.to_string()),
+                    signal_dbm: radiotap.signal_dbm,
+                }));
+            }
+            wifi::FrameKind::ProbeRequest => {
+                // Randomized (locally administered) MACs change every scan: the SSIDs they ask
+                // for are kept, but they do not become assets.
+                let randomized = management.source[0] & 0x02 != 0;
+                if randomized && management.ssid.is_none() {
+                    return None;
+                }
+                attributes.push(("role".to_string(), "wifi-client".to_string()));
+                if let Some(ssid) = management.ssid {
+                    observations.push(Observation::WifiProbe(WifiProbe {
+                        client_mac: mac.clone(),
+                        ssid,
+                        signal_dbm: radiotap.signal_dbm,
+                        randomized,
+                    }));
+                }
+                if randomized {
+                    mac = String::new();
+                }
+            }
+            wifi::FrameKind::AssociationRequest => {
+                attributes.push(("role".to_string(), "wifi-client".to_string()));
+                observations.push(Observation::WifiDevice(WifiDevice {
+                    mac_address: mac.clone(),
+                    role: "client".to_string(),
+                    ssid: management.ssid,
+                    bssid,
+                    channel,
+                    security: None,
+                    signal_dbm: radiotap.signal_dbm,
+                }));
+            }
+        }
+
+        Some(RawDiscovery {
+            mac,
+            ip: "0.0.0.0".to_string(),
+            method: "WiFi".to_string(),
+            hostname: None,
+            vlan_id: 0,
+            attributes,
+            observations,
+            ..Default::default()
+        })
+    }
+
+    fn process_packet(&self, value: &PacketHeaders, src_mac: &str, dst_mac: &str) -> Option<RawDiscovery> {
         let mut vlan_id = 1;
         if let Some(vlan) = value.vlan() {
             use etherparse::VlanHeader::*;
@@ -156,44 +446,96 @@ impl Sniffer {
             };
         }
 
-        let Some(link) = value.link else { return None; };
-        let LinkHeader::Ethernet2(eth) = link else { return None; };
-        
-        let src_mac = format!("{:02X}:{:02X}:{:02X}:{:02X}:{:02X}:{:02X}", 
-            eth.source[0], eth.source[1], eth.source[2], eth.source[3], eth.source[4], eth.source[5]);
-
-        if eth.ether_type == EtherType::ARP {
-            let arp_payload = value.payload.slice();
-            // ARP: sender IP starts at offset 14 (2+2+1+1+2+6 = hardware_type + proto_type + hw_len + proto_len + op + sender_hw)
-            if arp_payload.len() >= 28 {
-                let psrc = &arp_payload[14..18];
-                if Self::is_private_ip([psrc[0], psrc[1], psrc[2], psrc[3]]) {
+        if let Some(discovery) = self.process_fhrp(value, src_mac, vlan_id) {
+            return Some(discovery);
+        }
+
+        if let Some(discovery) = self.process_routing(value, src_mac, vlan_id) {
+            return Some(discovery);
+        }
+
+        if let Some(discovery) = self.process_ptp(value, src_mac, vlan_id) {
+            return Some(discovery);
+        }
+
+        if let Some(discovery) = self.process_vendor_discovery(value, src_mac, vlan_id) {
+            return Some(discovery);
+        }
+
+        if let Some(discovery) = self.process_browser(value, src_mac, vlan_id) {
+            return Some(discovery);
+        }
+
+        if let Some(discovery) = self.process_multicast(value, src_mac, vlan_id) {
+            return Some(discovery);
+        }
+
+        if let Some(discovery) = self.process_profinet(value, src_mac, vlan_id) {
+            return Some(discovery);
+        }
+
+        if let Some(discovery) = self.process_enip(value, src_mac, vlan_id) {
+            return Some(discovery);
+        }
+
+        if let Some(discovery) = self.process_bacnet(value, src_mac, vlan_id) {
+            return Some(discovery);
+        }
+
+        if let Some(discovery) = self.process_ot_session(value, src_mac, vlan_id) {
+            return Some(discovery);
+        }
+
+        if let Some(discovery) = self.process_automation_discovery(value, src_mac, vlan_id) {
+            return Some(discovery);
+        }
+
+        if let Some(discovery) = self.process_iot(value, src_mac, vlan_id) {
+            return Some(discovery);
+        }
+
+        if let Some(discovery) = self.process_iec61850(value, src_mac, dst_mac, vlan_id) {
+            return Some(discovery);
+        }
+
+        if let Some(discovery) = self.process_scada(value, src_mac, dst_mac, vlan_id) {
+            return Some(discovery);
+        }
+
+        if let Some(discovery) = self.process_ntp(value, src_mac, dst_mac, vlan_id) {
+            return Some(discovery);
+        }
+
+        // etherparse decodes ARP into `net`; the sender MAC comes from the ARP body so it is
+        // known even on link types without an Ethernet header.
+        if let Some(NetHeaders::Arp(arp)) = &value.net
+            && let Ok(arp) = arp.try_eth_ipv4()
+            && Self::is_private_ip(arp.sender_ipv4) {
+            return Some(RawDiscovery {
+                mac: protocols::format_mac(&arp.sender_mac),
+                ip: Ipv4Addr::from(arp.sender_ipv4).to_string(),
+                method: "ARP".to_string(),
+                hostname: None,
+                vlan_id,
+                ..Default::default()
+            });
+        }
+
+        if let Some(NetHeaders::Ipv6(ip6, _)) = &value.net
+            && let Some(TransportHeader::Icmpv6(icmp6)) = &value.transport {
+            match icmp6.icmp_type {
+                Icmpv6Type::NeighborSolicitation | Icmpv6Type::NeighborAdvertisement(_) | Icmpv6Type::RouterAdvertisement(_) => {
+                    let addr = Ipv6Addr::from(ip6.source);
                     return Some(RawDiscovery {
-                        mac: src_mac,
-                        ip: format!("{}.{}.{}.{}", psrc[0], psrc[1], psrc[2], psrc[3]),
-                        method: "ARP".to_string(),
+                        mac: src_mac.to_string(),
+                        ip: addr.to_string(),
+                        method: "NDP".to_string(),
                         hostname: None,
                         vlan_id,
+                        ..Default::default()
                     });
                 }
-            }
-        }
-
-        if let Some(NetHeaders::Ipv6(ip6, _)) = &value.net {
-            if let Some(TransportHeader::Icmpv6(icmp6)) = &value.transport {
-                match icmp6.icmp_type {
-                    Icmpv6Type::NeighborSolicitation | Icmpv6Type::NeighborAdvertisement(_) | Icmpv6Type::RouterAdvertisement(_) => {
-                        let addr = Ipv6Addr::from(ip6.source);
-                        return Some(RawDiscovery {
-                            mac: src_mac,
-                            ip: addr.to_string(),
-                            method: "NDP".to_string(),
-                            hostname: None,
-                            vlan_id,
-                        });
-                    }
-                    _ => {}
-                }
+                _ => {}
             }
         }
 
@@ -221,32 +563,808 @@ impl Sniffer {
                 }
             }
 
-            if let Some(m) = method {
-                if let Some(src_ip) = ip {
-                    return Some(RawDiscovery {
-                        mac: src_mac,
-                        ip: src_ip,
-                        method: m.to_string(),
-                        hostname,
-                        vlan_id,
-                    });
-                }
+            if let Some(m) = method
+                && let Some(src_ip) = ip {
+                return Some(RawDiscovery {
+                    mac: src_mac.to_string(),
+                    ip: src_ip,
+                    method: m.to_string(),
+                    hostname,
+                    vlan_id,
+                    ..Default::default()
+                });
             }
         }
 
-        if eth.ether_type == EtherType(0x88CC) || eth.ether_type == EtherType(0x2000) {
+        if let PayloadSlice::Ether(ether) = &value.payload
+            && (ether.ether_type == EtherType(0x88CC) || ether.ether_type == EtherType(0x2000)) {
             return Some(RawDiscovery {
-                mac: src_mac,
+                mac: src_mac.to_string(),
                 ip: "0.0.0.0".to_string(),
-                method: if eth.ether_type == EtherType(0x88CC) { "LLDP".to_string() } else { "CDP".to_string() },
+                method: if ether.ether_type == EtherType(0x88CC) { "LLDP".to_string() } else { "CDP".to_string() },
                 hostname: None,
                 vlan_id,
+                ..Default::default()
+            });
+        }
+
+        None
+    }
+
+    fn source_ip(value: &PacketHeaders) -> Option<IpAddr> {
+        match &value.net {
+            Some(NetHeaders::Ipv4(ipv4, _)) => Some(IpAddr::V4(Ipv4Addr::from(ipv4.source))),
+            Some(NetHeaders::Ipv6(ipv6, _)) => Some(IpAddr::V6(Ipv6Addr::from(ipv6.source))),
+            _ => None,
+        }
+    }
+
+    fn destination_ip(value: &PacketHeaders) -> Option<IpAddr> {
+        match &value.net {
+            Some(NetHeaders::Ipv4(ipv4, _)) => Some(IpAddr::V4(Ipv4Addr::from(ipv4.destination))),
+            Some(NetHeaders::Ipv6(ipv6, _)) => Some(IpAddr::V6(Ipv6Addr::from(ipv6.destination))),
+            _ => None,
+        }
+    }
+
+    /// HSRP/VRRP/GLBP hellos. The speaking router is recorded as a gateway under its real MAC;
+    /// the virtual MAC and IP only go into the group observation.
+    fn process_fhrp(&self, value: &PacketHeaders, src_mac: &str, vlan_id: u16) -> Option<RawDiscovery> {
+        let src_ip = Self::source_ip(value)?;
+
+        let packet = match (&value.transport, &value.payload) {
+            (Some(TransportHeader::Udp(udp)), PayloadSlice::Udp(payload)) => match udp.destination_port {
+                1985 | 2029 => fhrp::parse_hsrp(payload),
+                3222 => fhrp::parse_glbp(payload),
+                _ => None,
+            },
+            (None, PayloadSlice::Ip(ip_payload)) if ip_payload.ip_number.0 == 112 => {
+                fhrp::parse_vrrp(ip_payload.payload, src_ip.is_ipv6())
+            }
+            _ => None,
+        }?;
+
+        let virtual_mac = packet.virtual_mac.map(|m| protocols::format_mac(&m));
+        let virtual_ip = packet.virtual_ip.map(|ip| ip.to_string());
+        let burned_in = packet.member_mac.map(|m| protocols::format_mac(&m));
+        let member_mac = burned_in.clone().unwrap_or_else(|| src_mac.to_string());
+
+        // HSRPv1 and VRRP carry no burned-in MAC; an active/master speaking from the virtual
+        // MAC has no real MAC to key an asset on, so only the observation is kept.
+        let mac = match burned_in {
+            Some(mac) => mac,
+            None if virtual_mac.as_deref() == Some(src_mac) => String::new(),
+            None => src_mac.to_string(),
+        };
+
+        Some(RawDiscovery {
+            mac,
+            ip: src_ip.to_string(),
+            method: packet.protocol.to_string(),
+            hostname: None,
+            vlan_id,
+            attributes: vec![("role".to_string(), "gateway".to_string())],
+            observations: vec![Observation::Fhrp(FhrpHello {
+                protocol: packet.protocol.to_string(),
+                vlan_id,
+                group_id: packet.group_id,
+                virtual_ip,
+                virtual_mac,
+                priority: packet.priority,
+                state: packet.state.to_string(),
+                member_ip: src_ip.to_string(),
+                member_mac,
+            })],
+            ..Default::default()
+        })
+    }
+
+    /// OSPF, EIGRP, IS-IS, RIP and BGP speakers are tagged as routers.
+    fn process_routing(&self, value: &PacketHeaders, src_mac: &str, vlan_id: u16) -> Option<RawDiscovery> {
+        let src_ip = Self::source_ip(value);
+
+        let packet = match (&value.transport, &value.payload, src_ip) {
+            // IS-IS rides directly on 802.3/LLC, where the ether type field is a length.
+            (None, PayloadSlice::Ether(ether), _) if ether.ether_type.0 <= 1500 => routing::parse_isis(ether.payload),
+            (None, PayloadSlice::Ip(ip_payload), Some(ip)) => match ip_payload.ip_number.0 {
+                89 => routing::parse_ospf(ip_payload.payload),
+                88 => routing::parse_eigrp(ip_payload.payload, ip),
+                _ => None,
+            },
+            (Some(TransportHeader::Udp(udp)), PayloadSlice::Udp(payload), Some(ip)) if udp.destination_port == 520 => {
+                routing::parse_rip(payload, ip)
+            }
+            (Some(TransportHeader::Tcp(tcp)), PayloadSlice::Tcp(payload), Some(_)) if tcp.destination_port == 179 || tcp.source_port == 179 => {
+                routing::parse_bgp_open(payload, Self::destination_ip(value)?)
+            }
+            _ => None,
+        }?;
+
+        let ip = src_ip.or(packet.interface_ip).map(|ip| ip.to_string()).unwrap_or_else(|| "0.0.0.0".to_string());
+
+        Some(RawDiscovery {
+            mac: src_mac.to_string(),
+            ip: ip.clone(),
+            method: packet.protocol.to_string(),
+            hostname: None,
+            vlan_id,
+            attributes: vec![("role".to_string(), "router".to_string())],
+            observations: vec![Observation::Routing(RoutingHello {
+                protocol: packet.protocol.to_string(),
+                vlan_id,
+                router_id: packet.router_id,
+                area: packet.area,
+                neighbors: packet.neighbors,
+                speaker_ip: ip,
+                speaker_mac: src_mac.to_string(),
+            })],
+            ..Default::default()
+        })
+    }
+
+    /// IEEE 1588 PTPv2 over Ethernet (0x88F7) or UDP 319/320. Announce senders are
+    /// grandmasters or boundary clocks; Delay_Req senders reveal the slaves.
+    fn process_ptp(&self, value: &PacketHeaders, src_mac: &str, vlan_id: u16) -> Option<RawDiscovery> {
+        let payload = match (&value.transport, &value.payload) {
+            (None, PayloadSlice::Ether(ether)) if ether.ether_type == EtherType(0x88F7) => ether.payload,
+            (Some(TransportHeader::Udp(udp)), PayloadSlice::Udp(payload)) if matches!(udp.destination_port, 319 | 320) => *payload,
+            _ => return None,
+        };
+        let message = ptp::parse(payload)?;
+        if !matches!(message.message_type, ptp::MESSAGE_ANNOUNCE | ptp::MESSAGE_DELAY_REQ | ptp::MESSAGE_PDELAY_REQ) {
+            return None;
+        }
+
+        let ip = Self::source_ip(value).map(|ip| ip.to_string()).unwrap_or_else(|| "0.0.0.0".to_string());
+        let clock_identity = ptp::format_clock_identity(&message.clock_identity);
+        let mut attributes = vec![
+            ("ptp.clock_identity".to_string(), clock_identity.clone()),
+            ("ptp.domain".to_string(), message.domain.to_string()),
+        ];
+        let mut observations = Vec::new();
+
+        if let Some(announce) = message.announce {
+            let role = if announce.grandmaster_identity == message.clock_identity {
+                "grandmaster"
+            } else {
+                "boundary-clock"
+            };
+            attributes.push(("role".to_string(), role.to_string()));
+            observations.push(Observation::Ptp(PtpClock {
+                vlan_id,
+                domain: message.domain,
+                clock_identity,
+                grandmaster_identity: ptp::format_clock_identity(&announce.grandmaster_identity),
+                priority1: announce.priority1,
+                priority2: announce.priority2,
+                clock_class: announce.clock_class,
+                steps_removed: announce.steps_removed,
+                role: role.to_string(),
+                ip_address: ip.clone(),
+                mac_address: src_mac.to_string(),
+            }));
+        }
+
+        Some(RawDiscovery {
+            mac: src_mac.to_string(),
+            ip,
+            method: "PTP".to_string(),
+            hostname: None,
+            vlan_id,
+            attributes,
+            observations,
+            ..Default::default()
+        })
+    }
+
+    /// MikroTik MNDP (UDP 5678) and Ubiquiti discovery replies (UDP 10001). Both carry the
+    /// device's own MAC, name, board/model and firmware, which LLDP would otherwise provide.
+    fn process_vendor_discovery(&self, value: &PacketHeaders, src_mac: &str, vlan_id: u16) -> Option<RawDiscovery> {
+        let Some(TransportHeader::Udp(udp)) = &value.transport else { return None; };
+        let PayloadSlice::Udp(payload) = &value.payload else { return None; };
+        let src_ip = Self::source_ip(value).map(|ip| ip.to_string()).unwrap_or_else(|| "0.0.0.0".to_string());
+
+        if udp.source_port == 5678 && udp.destination_port == 5678 {
+            let announcement = mndp::parse(payload)?;
+            let attributes = [
+                ("mndp.platform", announcement.platform),
+                ("mndp.software_id", announcement.software_id),
+                ("mndp.interface", announcement.interface),
+            ]
+            .into_iter()
+            .filter_map(|(k, v)| v.map(|v| (k.to_string(), v)))
+            .collect();
+
+            return Some(RawDiscovery {
+                mac: announcement.mac.unwrap_or_else(|| src_mac.to_string()),
+                ip: announcement.ipv4.map(|ip| ip.to_string()).unwrap_or(src_ip),
+                method: "MNDP".to_string(),
+                hostname: announcement.identity,
+                model: announcement.board,
+                firmware: announcement.version,
+                vlan_id,
+                uptime: announcement.uptime,
+                attributes,
+                ..Default::default()
+            });
+        }
+
+        if udp.source_port == 10001 {
+            let announcement = ubnt::parse(payload)?;
+            let attributes = [
+                ("ubnt.platform", announcement.platform.clone()),
+                ("ubnt.essid", announcement.essid),
+            ]
+            .into_iter()
+            .filter_map(|(k, v)| v.map(|v| (k.to_string(), v)))
+            .collect();
+
+            return Some(RawDiscovery {
+                mac: announcement.mac.unwrap_or_else(|| src_mac.to_string()),
+                ip: src_ip,
+                method: "Ubiquiti".to_string(),
+                hostname: announcement.hostname,
+                model: announcement.model.or(announcement.platform),
+                firmware: announcement.firmware,
+                vlan_id,
+                uptime: announcement.uptime,
+                attributes,
+                ..Default::default()
             });
         }
 
         None
     }
 
+    /// SMB Browser announcements (NetBIOS datagram, UDP 138) from Windows and Samba hosts.
+    fn process_browser(&self, value: &PacketHeaders, src_mac: &str, vlan_id: u16) -> Option<RawDiscovery> {
+        let Some(TransportHeader::Udp(udp)) = &value.transport else { return None; };
+        let PayloadSlice::Udp(payload) = &value.payload else { return None; };
+        if udp.destination_port != 138 {
+            return None;
+        }
+        let announcement = browser::parse(payload)?;
+
+        let mut attributes: Vec<(String, String)> = browser::server_roles(announcement.server_type)
+            .into_iter()
+            .map(|role| ("role".to_string(), role.to_string()))
+            .collect();
+        attributes.push(("smb.server_type".to_string(), format!("0x{:08X}", announcement.server_type)));
+
+        // DomainAnnouncement names the workgroup and carries the sender's name as its comment.
+        let hostname = if announcement.kind == "DomainAnnouncement" {
+            attributes.push(("smb.workgroup".to_string(), announcement.name.clone()));
+            announcement.comment.clone()
+        } else {
+            if let Some(workgroup) = &announcement.destination {
+                attributes.push(("smb.workgroup".to_string(), workgroup.clone()));
+            }
+            if let Some(comment) = &announcement.comment {
+                attributes.push(("smb.comment".to_string(), comment.clone()));
+            }
+            Some(announcement.name.clone())
+        };
+
+        Some(RawDiscovery {
+            mac: src_mac.to_string(),
+            ip: Self::source_ip(value)?.to_string(),
+            method: "SMB Browser".to_string(),
+            hostname,
+            os_guess: browser::os_guess(&announcement),
+            vlan_id,
+            attributes,
+            ..Default::default()
+        })
+    }
+
+    /// IGMPv1/v2/v3 (IPv4 protocol 2) and MLDv1/v2 (ICMPv6 130-132, 143). Reports and leaves
+    /// update the sender's group memberships; queries identify the VLAN's querier.
+    fn process_multicast(&self, value: &PacketHeaders, src_mac: &str, vlan_id: u16) -> Option<RawDiscovery> {
+        let message = match (&value.transport, &value.payload) {
+            (None, PayloadSlice::Ip(ip_payload)) if ip_payload.ip_number.0 == 2 => multicast::parse_igmp(ip_payload.payload)?,
+            (Some(TransportHeader::Icmpv6(icmp6)), PayloadSlice::Icmpv6(payload)) => {
+                let Icmpv6Type::Unknown { type_u8, bytes5to8, .. } = icmp6.icmp_type else { return None; };
+                let mut body = bytes5to8.to_vec();
+                body.extend_from_slice(payload);
+                multicast::parse_mld(type_u8, &body)?
+            }
+            _ => return None,
+        };
+        let ip = Self::source_ip(value)?.to_string();
+
+        let (attributes, observations) = match message {
+            multicast::MulticastMessage::Query { protocol } => (
+                vec![("role".to_string(), "multicast-querier".to_string())],
+                vec![Observation::MulticastQuerier(MulticastQuerier {
+                    protocol: protocol.to_string(),
+                    vlan_id,
+                    querier_ip: ip.clone(),
+                    querier_mac: src_mac.to_string(),
+                })],
+            ),
+            multicast::MulticastMessage::Report { protocol, joined, left } => {
+                let attributes = joined.iter()
+                    .filter_map(multicast::service_name)
+                    .map(|service| ("multicast.service".to_string(), service.to_string()))
+                    .collect();
+                let observations = joined.iter().map(|group| (group, true))
+                    .chain(left.iter().map(|group| (group, false)))
+                    .map(|(group, joined)| Observation::Multicast(MulticastMembership {
+                        protocol: protocol.to_string(),
+                        vlan_id,
+                        group_address: group.to_string(),
+                        joined,
+                        member_ip: ip.clone(),
+                        member_mac: src_mac.to_string(),
+                    }))
+                    .collect();
+                (attributes, observations)
+            }
+        };
+
+        Some(RawDiscovery {
+            mac: src_mac.to_string(),
+            ip,
+            method: "IGMP/MLD".to_string(),
+            hostname: None,
+            vlan_id,
+            attributes,
+            observations,
+            ..Default::default()
+        })
+    }
+
+    /// NTP (UDP 123). Client requests and server replies both yield the client/server pair;
+    /// replies and broadcasts also describe the server. Only the private side becomes an asset,
+    /// so public servers on the Internet are recorded as peers but never inventoried.
+    fn process_ntp(&self, value: &PacketHeaders, src_mac: &str, dst_mac: &str, vlan_id: u16) -> Option<RawDiscovery> {
+        let Some(TransportHeader::Udp(udp)) = &value.transport else { return None; };
+        let PayloadSlice::Udp(payload) = &value.payload else { return None; };
+        if udp.source_port != 123 && udp.destination_port != 123 {
+            return None;
+        }
+        let packet = ntp::parse(payload)?;
+        let src_ip = Self::source_ip(value)?;
+        let dst_ip = Self::destination_ip(value)?;
+
+        let mut observations = Vec::new();
+        if matches!(packet.mode, ntp::MODE_SERVER | ntp::MODE_BROADCAST) {
+            observations.push(Observation::NtpServer(NtpServer {
+                vlan_id,
+                server_ip: src_ip.to_string(),
+                version: packet.version,
+                stratum: packet.stratum,
+                reference_id: ntp::reference_id_text(&packet),
+            }));
+        }
+
+        let (client, client_mac, server) = match packet.mode {
+            ntp::MODE_CLIENT => (src_ip, src_mac, dst_ip),
+            ntp::MODE_SERVER | ntp::MODE_BROADCAST => (dst_ip, dst_mac, src_ip),
+            _ => return None,
+        };
+        if packet.mode != ntp::MODE_BROADCAST {
+            observations.push(Observation::NtpAssociation(NtpAssociation {
+                vlan_id,
+                client_ip: client.to_string(),
+                client_mac: client_mac.to_string(),
+                server_ip: server.to_string(),
+                server_public: !Self::is_private_addr(&server),
+                version: packet.version,
+            }));
+        }
+
+        // The sender is the asset when it is ours; otherwise the local client it talks to.
+        let (mac, ip, role) = if Self::is_private_addr(&src_ip) {
+            let role = if packet.mode == ntp::MODE_CLIENT { "ntp-client" } else { "ntp-server" };
+            (src_mac, src_ip, role)
+        } else if packet.mode == ntp::MODE_SERVER && Self::is_private_addr(&dst_ip) {
+            (dst_mac, dst_ip, "ntp-client")
+        } else {
+            return None;
+        };
+
+        Some(RawDiscovery {
+            mac: mac.to_string(),
+            ip: ip.to_string(),
+            method: "NTP".to_string(),
+            hostname: None,
+            vlan_id,
+            attributes: vec![("role".to_string(), role.to_string())],
+            observations,
+            ..Default::default()
+        })
+    }
+
+    /// PROFINET DCP Identify responses and Hello requests (ether type 0x8892).
+    fn process_profinet(&self, value: &PacketHeaders, src_mac: &str, vlan_id: u16) -> Option<RawDiscovery> {
+        let PayloadSlice::Ether(ether) = &value.payload else { return None; };
+        if ether.ether_type != EtherType(0x8892) {
+            return None;
+        }
+        let identity = profinet::parse_dcp(ether.payload)?;
+
+        let mut attributes = Vec::new();
+        if let Some(vendor_id) = identity.vendor_id {
+            attributes.push(("profinet.vendor_id".to_string(), format!("0x{:04X}", vendor_id)));
+        }
+        if let Some(device_id) = identity.device_id {
+            attributes.push(("profinet.device_id".to_string(), format!("0x{:04X}", device_id)));
+        }
+        if let Some(station_type) = identity.type_of_station {
+            attributes.push(("profinet.station_type".to_string(), station_type));
+        }
+        for role in identity.roles {
+            attributes.push(("role".to_string(), role.to_string()));
+        }
+
+        Some(RawDiscovery {
+            mac: src_mac.to_string(),
+            ip: identity.ip.map(|ip| ip.to_string()).unwrap_or_else(|| "0.0.0.0".to_string()),
+            method: "PROFINET".to_string(),
+            hostname: identity.name_of_station,
+            vlan_id,
+            attributes,
+            ..Default::default()
+        })
+    }
+
+    /// EtherNet/IP ListIdentity responses on UDP/TCP 44818.
+    fn process_enip(&self, value: &PacketHeaders, src_mac: &str, vlan_id: u16) -> Option<RawDiscovery> {
+        let payload = match (&value.transport, &value.payload) {
+            (Some(TransportHeader::Udp(udp)), PayloadSlice::Udp(payload)) if udp.source_port == 44818 || udp.destination_port == 44818 => payload,
+            (Some(TransportHeader::Tcp(tcp)), PayloadSlice::Tcp(payload)) if tcp.source_port == 44818 || tcp.destination_port == 44818 => payload,
+            _ => return None,
+        };
+        let identity = enip::parse_list_identity(payload)?;
+        let src_ip = Self::source_ip(value)?;
+
+        let device_type = match enip::device_type_name(identity.device_type) {
+            Some(name) => format!("{} ({})", identity.device_type, name),
+            None => identity.device_type.to_string(),
+        };
+        let mut attributes = vec![
+            ("enip.vendor_id".to_string(), identity.vendor_id.to_string()),
+            ("enip.device_type".to_string(), device_type),
+            ("enip.product_code".to_string(), identity.product_code.to_string()),
+            ("enip.revision".to_string(), format!("{}.{:03}", identity.revision.0, identity.revision.1)),
+            ("enip.serial_number".to_string(), format!("0x{:08X}", identity.serial_number)),
+        ];
+        if let Some(name) = identity.product_name {
+            attributes.push(("enip.product_name".to_string(), name));
+        }
+        if identity.device_type == 0x0E {
+            attributes.push(("role".to_string(), "plc".to_string()));
+        }
+
+        Some(RawDiscovery {
+            mac: src_mac.to_string(),
+            ip: src_ip.to_string(),
+            method: "EtherNet/IP".to_string(),
+            hostname: None,
+            vlan_id,
+            attributes,
+            ..Default::default()
+        })
+    }
+
+    /// BACnet/IP I-Am, I-Have and Who-Is on UDP 47808. Frames relayed by a BBMD or a
+    /// BACnet router describe a remote device, so only the relay becomes the asset.
+    fn process_bacnet(&self, value: &PacketHeaders, src_mac: &str, vlan_id: u16) -> Option<RawDiscovery> {
+        let (Some(TransportHeader::Udp(udp)), PayloadSlice::Udp(payload)) = (&value.transport, &value.payload) else { return None; };
+        if udp.source_port != 47808 && udp.destination_port != 47808 {
+            return None;
+        }
+        let packet = bacnet::parse(payload)?;
+        let src_ip = Self::source_ip(value)?;
+        let relayed = packet.forwarded_from.is_some() || packet.source_network.is_some();
+
+        let mut attributes = Vec::new();
+        if packet.forwarded_from.is_some() {
+            attributes.push(("role".to_string(), "bbmd".to_string()));
+        } else if packet.source_network.is_some() {
+            attributes.push(("role".to_string(), "bacnet-router".to_string()));
+        } else if let Some(instance) = packet.device_instance {
+            attributes.push(("bacnet.device_instance".to_string(), instance.to_string()));
+            if let Some(vendor_id) = packet.vendor_id {
+                attributes.push(("bacnet.vendor_id".to_string(), vendor_id.to_string()));
+            }
+            if let Some(max_apdu) = packet.max_apdu {
+                attributes.push(("bacnet.max_apdu".to_string(), max_apdu.to_string()));
+            }
+            if let Some(segmentation) = packet.segmentation {
+                attributes.push(("bacnet.segmentation".to_string(), segmentation.to_string()));
+            }
+        }
+
+        let observations = packet.device_instance.map(|device_instance| Observation::Bacnet(BacnetDevice {
+            device_instance,
+            vlan_id,
+            ip_address: packet.forwarded_from.map(IpAddr::V4).unwrap_or(src_ip).to_string(),
+            mac_address: (!relayed).then(|| src_mac.to_string()),
+            network: packet.source_network.as_ref().map(|(net, _)| *net),
+            network_address: packet.source_network.as_ref().map(|(_, addr)| {
+                addr.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(":")
+            }),
+            forwarded_by: packet.forwarded_from.map(|_| src_ip.to_string()),
+            vendor_id: packet.vendor_id,
+            max_apdu: packet.max_apdu,
+            segmentation: packet.segmentation.map(str::to_string),
+            object_name: packet.object_name,
+        })).into_iter().collect();
+
+        Some(RawDiscovery {
+            mac: src_mac.to_string(),
+            ip: src_ip.to_string(),
+            method: "BACnet".to_string(),
+            hostname: None,
+            vlan_id,
+            attributes,
+            observations,
+            ..Default::default()
+        })
+    }
+
+    /// Modbus/TCP (502) and S7comm (102) sessions. Whoever talks to the well-known port is
+    /// the master (HMI, SCADA, engineering station); whoever answers from it is the slave (PLC).
+    fn process_ot_session(&self, value: &PacketHeaders, src_mac: &str, vlan_id: u16) -> Option<RawDiscovery> {
+        let (Some(TransportHeader::Tcp(tcp)), PayloadSlice::Tcp(payload)) = (&value.transport, &value.payload) else { return None; };
+
+        let (protocol, server_port, mut attributes) = if tcp.source_port == 502 || tcp.destination_port == 502 {
+            let segment = modbus::parse(payload)?;
+            let mut attributes: Vec<(String, String)> = segment.unit_ids.iter()
+                .map(|id| ("modbus.unit_id".to_string(), id.to_string()))
+                .collect();
+            attributes.extend(segment.function_codes.iter().map(|&fc| {
+                let value = match modbus::function_name(fc) {
+                    Some(name) => format!("{} ({})", fc, name),
+                    None => fc.to_string(),
+                };
+                ("modbus.function_code".to_string(), value)
+            }));
+            ("Modbus/TCP", 502, attributes)
+        } else if tcp.source_port == 102 || tcp.destination_port == 102 {
+            let pdu = s7comm::parse(payload)?;
+            let mut attributes: Vec<(String, String)> = pdu.identification.into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect();
+            if let Some(function) = pdu.function {
+                attributes.push(("s7.function".to_string(), function.to_string()));
+            }
+            ("S7comm", 102, attributes)
+        } else {
+            return None;
+        };
+
+        let role = if tcp.destination_port == server_port { "master" } else { "slave" };
+        attributes.push(("ot.role".to_string(), role.to_string()));
+        attributes.push(("ot.protocol".to_string(), protocol.to_string()));
+
+        Some(RawDiscovery {
+            mac: src_mac.to_string(),
+            ip: Self::source_ip(value)?.to_string(),
+            method: protocol.to_string(),
+            hostname: None,
+            vlan_id,
+            attributes,
+            ..Default::default()
+        })
+    }
+
+    /// IEC 61850 GOOSE (0x88B8) and Sampled Values (0x88BA) publishers. IEDs have no IP
+    /// identity here, so the stream is attributed to the publishing MAC.
+    fn process_iec61850(&self, value: &PacketHeaders, src_mac: &str, dst_mac: &str, vlan_id: u16) -> Option<RawDiscovery> {
+        let PayloadSlice::Ether(ether) = &value.payload else { return None; };
+        let stream = iec61850::parse(ether.ether_type.0, ether.payload)?;
+
+        // SV runs at thousands of frames per second; smpCnt restarts every second,
+        // so one frame per stream and second is plenty for an inventory.
+        if stream.smp_cnt.is_some_and(|count| count != 0) {
+            return None;
+        }
+
+        Some(RawDiscovery {
+            mac: src_mac.to_string(),
+            ip: "0.0.0.0".to_string(),
+            method: stream.protocol.to_string(),
+            hostname: None,
+            vlan_id,
+            attributes: vec![("role".to_string(), "ied".to_string())],
+            observations: vec![Observation::Iec61850(Iec61850Publisher {
+                protocol: stream.protocol.to_string(),
+                vlan_id,
+                appid: stream.appid,
+                control_ref: stream.control_ref,
+                dataset: stream.dataset,
+                conf_rev: stream.conf_rev,
+                publisher_mac: src_mac.to_string(),
+                destination_mac: dst_mac.to_string(),
+            })],
+            ..Default::default()
+        })
+    }
+
+    /// KNXnet/IP search/description responses (UDP 3671) and OPC UA FindServers/GetEndpoints
+    /// responses (TCP 4840), both answered by the device being described.
+    fn process_automation_discovery(&self, value: &PacketHeaders, src_mac: &str, vlan_id: u16) -> Option<RawDiscovery> {
+        let src_ip = Self::source_ip(value)?.to_string();
+
+        match (&value.transport, &value.payload) {
+            (Some(TransportHeader::Udp(udp)), PayloadSlice::Udp(payload)) if udp.source_port == 3671 || udp.destination_port == 3671 => {
+                let device = knx::parse(payload)?;
+                // The DIB carries the device's own MAC, which survives routed searches.
+                let mac = if device.mac == [0; 6] { src_mac.to_string() } else { protocols::format_mac(&device.mac) };
+
+                Some(RawDiscovery {
+                    mac,
+                    ip: src_ip,
+                    method: "KNXnet/IP".to_string(),
+                    hostname: device.friendly_name,
+                    vlan_id,
+                    attributes: vec![
+                        ("knx.serial_number".to_string(), device.serial_number),
+                        ("knx.individual_address".to_string(), device.individual_address),
+                    ],
+                    ..Default::default()
+                })
+            }
+            (Some(TransportHeader::Tcp(tcp)), PayloadSlice::Tcp(payload)) if tcp.source_port == 4840 => {
+                let server = opcua::parse(payload)?;
+                let attributes = [
+                    ("opcua.application_uri", server.application_uri),
+                    ("opcua.product_uri", server.product_uri),
+                    ("opcua.application_name", server.application_name),
+                ]
+                .into_iter()
+                .filter_map(|(k, v)| v.map(|v| (k.to_string(), v)))
+                .collect();
+
+                Some(RawDiscovery {
+                    mac: src_mac.to_string(),
+                    ip: src_ip,
+                    method: "OPC UA".to_string(),
+                    hostname: None,
+                    vlan_id,
+                    attributes,
+                    ..Default::default()
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// MQTT CONNECT (TCP 1883), whose client ID names the device, and CoAP `/.well-known/core`
+    /// responses (UDP 5683) listing the resources a constrained device serves.
+    fn process_iot(&self, value: &PacketHeaders, src_mac: &str, vlan_id: u16) -> Option<RawDiscovery> {
+        let src_ip = Self::source_ip(value)?.to_string();
+
+        match (&value.transport, &value.payload) {
+            (Some(TransportHeader::Tcp(tcp)), PayloadSlice::Tcp(payload)) if tcp.destination_port == 1883 => {
+                let connect = mqtt::parse(payload)?;
+                let protocol_version = mqtt::protocol_name(connect.protocol_level);
+
+                let mut attributes = vec![
+                    ("role".to_string(), "mqtt-client".to_string()),
+                    ("mqtt.protocol".to_string(), protocol_version.clone()),
+                ];
+                if let Some(client_id) = &connect.client_id {
+                    attributes.push(("mqtt.client_id".to_string(), client_id.clone()));
+                }
+                if let Some(username) = &connect.username {
+                    attributes.push(("mqtt.username".to_string(), username.clone()));
+                }
+
+                Some(RawDiscovery {
+                    mac: src_mac.to_string(),
+                    ip: src_ip.clone(),
+                    method: "MQTT".to_string(),
+                    hostname: connect.client_id.clone(),
+                    vlan_id,
+                    attributes,
+                    observations: vec![Observation::Mqtt(MqttSession {
+                        vlan_id,
+                        client_ip: src_ip,
+                        client_mac: src_mac.to_string(),
+                        client_id: connect.client_id,
+                        username: connect.username,
+                        protocol_version,
+                        broker_ip: Self::destination_ip(value)?.to_string(),
+                        broker_port: tcp.destination_port,
+                    })],
+                    ..Default::default()
+                })
+            }
+            (Some(TransportHeader::Udp(udp)), PayloadSlice::Udp(payload)) if udp.source_port == 5683 => {
+                let resources = coap::parse_link_format(payload)?;
+                let mut attributes = vec![("role".to_string(), "coap-server".to_string())];
+                for resource in resources {
+                    attributes.push(("coap.resource".to_string(), resource.path));
+                    if let Some(resource_type) = resource.resource_type {
+                        attributes.push(("coap.resource_type".to_string(), resource_type));
+                    }
+                    if let Some(interface) = resource.interface {
+                        attributes.push(("coap.interface".to_string(), interface));
+                    }
+                }
+
+                Some(RawDiscovery {
+                    mac: src_mac.to_string(),
+                    ip: src_ip,
+                    method: "CoAP".to_string(),
+                    hostname: None,
+                    vlan_id,
+                    attributes,
+                    ..Default::default()
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// DNP3 (TCP/UDP 20000) and IEC 60870-5-104 (TCP 2404). Both ends of the conversation
+    /// are recorded as a master/outstation pair; the sender becomes the asset.
+    fn process_scada(&self, value: &PacketHeaders, src_mac: &str, dst_mac: &str, vlan_id: u16) -> Option<RawDiscovery> {
+        let (src_port, dst_port, payload) = match (&value.transport, &value.payload) {
+            (Some(TransportHeader::Tcp(tcp)), PayloadSlice::Tcp(payload)) => (tcp.source_port, tcp.destination_port, *payload),
+            (Some(TransportHeader::Udp(udp)), PayloadSlice::Udp(payload)) => (udp.source_port, udp.destination_port, *payload),
+            _ => return None,
+        };
+        let src_ip = Self::source_ip(value)?.to_string();
+        let dst_ip = Self::destination_ip(value)?.to_string();
+
+        // (protocol, sender is master, sender's address, master address, outstation address)
+        let (protocol, from_master, own_address, master_address, outstation_address) = if src_port == 20000 || dst_port == 20000 {
+            let link = dnp3::parse(payload)?;
+            let (master, outstation) = if link.from_master {
+                (link.source, link.destination)
+            } else {
+                (link.destination, link.source)
+            };
+            ("DNP3", link.from_master, ("dnp3.address", link.source), Some(master), outstation)
+        } else if (src_port == 2404 || dst_port == 2404) && matches!(value.transport, Some(TransportHeader::Tcp(_))) {
+            let asdu = iec104::parse(payload)?;
+            // The controlling station opens the connection to port 2404.
+            let from_master = dst_port == 2404;
+            let own_address = if from_master {
+                ("iec104.originator_address", u16::from(asdu.originator_address))
+            } else {
+                ("iec104.common_address", asdu.common_address)
+            };
+            ("IEC-104", from_master, own_address, Some(u16::from(asdu.originator_address)), asdu.common_address)
+        } else {
+            return None;
+        };
+
+        let (master_ip, master_mac, outstation_ip, outstation_mac) = if from_master {
+            (src_ip.clone(), src_mac, dst_ip, dst_mac)
+        } else {
+            (dst_ip, dst_mac, src_ip.clone(), src_mac)
+        };
+
+        Some(RawDiscovery {
+            mac: src_mac.to_string(),
+            ip: src_ip,
+            method: protocol.to_string(),
+            hostname: None,
+            vlan_id,
+            attributes: vec![
+                ("scada.role".to_string(), if from_master { "master" } else { "outstation" }.to_string()),
+                (own_address.0.to_string(), own_address.1.to_string()),
+            ],
+            observations: vec![Observation::Scada(ScadaLink {
+                protocol: protocol.to_string(),
+                vlan_id,
+                master_ip,
+                master_mac: master_mac.to_string(),
+                master_address,
+                outstation_ip,
+                outstation_mac: outstation_mac.to_string(),
+                outstation_address,
+            })],
+            ..Default::default()
+        })
+    }
+
     fn extract_hostname_from_dns(&self, payload: &[u8]) -> Option<String> {
         if payload.len() < 13 { return None; }
         let mut pos = 12; 
@@ -256,10 +1374,9 @@ impl Sniffer {
             pos += 1;
             if pos + len <= payload.len() {
                 let segment = &payload[pos..pos+len];
-                if let Ok(s) = std::str::from_utf8(segment) {
-                    if s.len() > 2 && s.chars().all(|c| c.is_alphanumeric() || c == '-') {
-                        return Some(s.to_string());
-                    }
+                if let Ok(s) = std::str::from_utf8(segment)
+                    && s.len() > 2 && s.chars().all(|c| c.is_alphanumeric() || c == '-') {
+                    return Some(s.to_string());
                 }
                 pos += len;
             } else { break; }
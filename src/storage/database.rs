@@ -1,9 +1,26 @@
-use rusqlite::{params, Result};
+use rusqlite::{params, Connection, OptionalExtension, Result};
 use r2d2::Pool;
 use r2d2_sqlite::SqliteConnectionManager;
-use crate::domain::models::Asset;
+use crate::domain::models::{Asset, Observation, FhrpHello, RoutingHello, BacnetDevice, Iec61850Publisher, ScadaLink, PtpClock, MulticastMembership, MulticastQuerier, NtpServer, NtpAssociation, MqttSession, WifiDevice, WifiProbe, FlowHost, EvidenceRef, BOOT_TIME_FORMAT, CONFIDENCE_WIRE};
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
 use std::path::Path;
 
+/// How long a redundancy group is observed before new members are reported as unexpected.
+const FHRP_LEARNING_WINDOW_MINUTES: i64 = 5;
+
+/// `booted_at` readings this close together are the same boot: uptime counters and capture
+/// times jitter by a second or two and drift slowly.
+const BOOT_TIME_TOLERANCE_SECONDS: i64 = 60;
+
+/// An NTP association with the client's hostname and the server's stratum, when known.
+pub type NtpReportRow = (NtpAssociation, Option<String>, Option<u8>);
+
+/// A flow host with its first and last report time.
+pub type FlowReportRow = (FlowHost, DateTime<Utc>, DateTime<Utc>);
+
+/// A fact with the frame that first established it and when that frame was captured.
+pub type EvidenceRow = (String, EvidenceRef, DateTime<Utc>);
+
 pub struct Database {
     pool: Pool<SqliteConnectionManager>,
 }
@@ -32,49 +49,366 @@ impl Database {
                 ip_address TEXT,
                 hostname TEXT,
                 vendor TEXT,
+                model TEXT,
+                firmware TEXT,
+                os_guess TEXT,
                 vlan_id INTEGER,
+                segment TEXT,
                 discovery_method TEXT,
+                confidence INTEGER,
                 first_seen_at DATETIME,
                 last_seen_at DATETIME
             )",
             [],
         )?;
 
+        // Columns added after the first release; older databases get them on open.
+        Self::add_column_if_missing(&conn, "assets", "model", "TEXT")?;
+        Self::add_column_if_missing(&conn, "assets", "firmware", "TEXT")?;
+        Self::add_column_if_missing(&conn, "assets", "os_guess", "TEXT")?;
+        Self::add_column_if_missing(&conn, "assets", "segment", "TEXT")?;
+        Self::add_column_if_missing(&conn, "assets", "confidence", &format!("INTEGER DEFAULT {}", CONFIDENCE_WIRE))?;
+
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_assets_last_seen ON assets(last_seen_at DESC)",
             [],
         )?;
 
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS asset_attributes (
+                mac_address TEXT,
+                key TEXT,
+                value TEXT,
+                first_seen_at DATETIME,
+                last_seen_at DATETIME,
+                PRIMARY KEY (mac_address, key, value)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS fhrp_groups (
+                vlan_id INTEGER,
+                protocol TEXT,
+                group_id INTEGER,
+                member_ip TEXT,
+                member_mac TEXT,
+                virtual_ip TEXT,
+                virtual_mac TEXT,
+                priority INTEGER,
+                state TEXT,
+                first_seen_at DATETIME,
+                last_seen_at DATETIME,
+                PRIMARY KEY (vlan_id, protocol, group_id, member_ip)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS routers (
+                protocol TEXT,
+                router_id TEXT,
+                area TEXT,
+                vlan_id INTEGER,
+                ip_address TEXT,
+                mac_address TEXT,
+                first_seen_at DATETIME,
+                last_seen_at DATETIME,
+                PRIMARY KEY (protocol, router_id)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS routing_adjacencies (
+                protocol TEXT,
+                router_id TEXT,
+                neighbor_id TEXT,
+                vlan_id INTEGER,
+                first_seen_at DATETIME,
+                last_seen_at DATETIME,
+                PRIMARY KEY (protocol, router_id, neighbor_id)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS bacnet_devices (
+                device_instance INTEGER PRIMARY KEY,
+                vlan_id INTEGER,
+                ip_address TEXT,
+                mac_address TEXT,
+                network INTEGER,
+                network_address TEXT,
+                forwarded_by TEXT,
+                vendor_id INTEGER,
+                max_apdu INTEGER,
+                segmentation TEXT,
+                object_name TEXT,
+                first_seen_at DATETIME,
+                last_seen_at DATETIME
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS iec61850_publishers (
+                vlan_id INTEGER,
+                protocol TEXT,
+                control_ref TEXT,
+                publisher_mac TEXT,
+                appid INTEGER,
+                dataset TEXT,
+                conf_rev INTEGER,
+                destination_mac TEXT,
+                first_seen_at DATETIME,
+                last_seen_at DATETIME,
+                PRIMARY KEY (vlan_id, protocol, control_ref, publisher_mac)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS scada_links (
+                protocol TEXT,
+                master_ip TEXT,
+                outstation_ip TEXT,
+                outstation_address INTEGER,
+                master_mac TEXT,
+                master_address INTEGER,
+                outstation_mac TEXT,
+                vlan_id INTEGER,
+                first_seen_at DATETIME,
+                last_seen_at DATETIME,
+                PRIMARY KEY (protocol, master_ip, outstation_ip, outstation_address)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS ptp_clocks (
+                domain INTEGER,
+                clock_identity TEXT,
+                vlan_id INTEGER,
+                ip_address TEXT,
+                mac_address TEXT,
+                role TEXT,
+                grandmaster_identity TEXT,
+                priority1 INTEGER,
+                priority2 INTEGER,
+                clock_class INTEGER,
+                steps_removed INTEGER,
+                first_seen_at DATETIME,
+                last_seen_at DATETIME,
+                PRIMARY KEY (domain, clock_identity)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS ptp_domains (
+                vlan_id INTEGER,
+                domain INTEGER,
+                grandmaster_identity TEXT,
+                changed_at DATETIME,
+                last_seen_at DATETIME,
+                PRIMARY KEY (vlan_id, domain)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS multicast_memberships (
+                mac_address TEXT,
+                group_address TEXT,
+                vlan_id INTEGER,
+                ip_address TEXT,
+                protocol TEXT,
+                first_seen_at DATETIME,
+                last_seen_at DATETIME,
+                PRIMARY KEY (mac_address, group_address)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS multicast_queriers (
+                vlan_id INTEGER,
+                family TEXT,
+                querier_ip TEXT,
+                querier_mac TEXT,
+                protocol TEXT,
+                first_seen_at DATETIME,
+                last_seen_at DATETIME,
+                PRIMARY KEY (vlan_id, family)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS ntp_servers (
+                server_ip TEXT PRIMARY KEY,
+                vlan_id INTEGER,
+                version INTEGER,
+                stratum INTEGER,
+                reference_id TEXT,
+                first_seen_at DATETIME,
+                last_seen_at DATETIME
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS ntp_associations (
+                client_ip TEXT,
+                server_ip TEXT,
+                client_mac TEXT,
+                vlan_id INTEGER,
+                server_public INTEGER,
+                version INTEGER,
+                first_seen_at DATETIME,
+                last_seen_at DATETIME,
+                PRIMARY KEY (client_ip, server_ip)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS mqtt_sessions (
+                client_mac TEXT,
+                broker_ip TEXT,
+                broker_port INTEGER,
+                client_ip TEXT,
+                vlan_id INTEGER,
+                client_id TEXT,
+                username TEXT,
+                protocol_version TEXT,
+                first_seen_at DATETIME,
+                last_seen_at DATETIME,
+                PRIMARY KEY (client_mac, broker_ip, broker_port)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS wifi_devices (
+                mac_address TEXT PRIMARY KEY,
+                role TEXT,
+                ssid TEXT,
+                bssid TEXT,
+                channel INTEGER,
+                security TEXT,
+                signal_dbm INTEGER,
+                first_seen_at DATETIME,
+                last_seen_at DATETIME
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS wifi_probes (
+                client_mac TEXT,
+                ssid TEXT,
+                signal_dbm INTEGER,
+                randomized INTEGER,
+                first_seen_at DATETIME,
+                last_seen_at DATETIME,
+                PRIMARY KEY (client_mac, ssid)
+            )",
+            [],
+        )?;
+        Self::add_column_if_missing(&conn, "wifi_probes", "randomized", "INTEGER DEFAULT 0")?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS flow_hosts (
+                ip_address TEXT,
+                exporter TEXT,
+                mac_address TEXT,
+                vlan_id INTEGER,
+                bytes INTEGER,
+                packets INTEGER,
+                first_seen_at DATETIME,
+                last_seen_at DATETIME,
+                PRIMARY KEY (ip_address, exporter)
+            )",
+            [],
+        )?;
+
+        // The first frame behind each fact; later sightings of the same fact add nothing new.
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS evidence (
+                mac_address TEXT,
+                fact TEXT,
+                file TEXT,
+                packet_number INTEGER,
+                captured_at DATETIME,
+                PRIMARY KEY (mac_address, fact)
+            )",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    fn add_column_if_missing(conn: &Connection, table: &str, column: &str, definition: &str) -> Result<()> {
+        let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+        let exists = stmt
+            .query_map([], |row| row.get::<_, String>(1))?
+            .filter_map(|name| name.ok())
+            .any(|name| name == column);
+        if !exists {
+            conn.execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition), [])?;
+        }
         Ok(())
     }
 
     /// Optimized UPSERT logic:
     /// - Matches by mac_address (Primary Key).
-    /// - If it exists: Updates IP, hostname, vendor, vlan, method and last_seen.
-    /// - Does NOT update first_seen_at (preserving history).
+    /// - If it exists: Updates IP, hostname, vendor, model, firmware, OS guess, vlan, segment, method and last_seen.
+    /// - Keeps the known IP when the sighting is L2-only (`0.0.0.0`, e.g. LLDP or IS-IS).
+    /// - Keeps IP, vlan, segment and method when the sighting is less trusted than the stored one
+    ///   (flow records vs. frames on the wire) or older than it; confidence only ever goes up.
+    /// - first_seen_at/last_seen_at only widen, so packets processed out of capture order
+    ///   (several files, a channel backlog) never move them backwards.
     pub fn sync_asset(&self, asset: &Asset) -> Result<()> {
         let conn = self.pool.get().expect("Failed to get connection from pool");
         
         conn.execute(
             "INSERT INTO assets (
-                mac_address, ip_address, hostname, vendor, vlan_id, 
-                discovery_method, first_seen_at, last_seen_at
+                mac_address, ip_address, hostname, vendor, model, firmware, os_guess, vlan_id,
+                segment, discovery_method, confidence, first_seen_at, last_seen_at
             )
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
             ON CONFLICT(mac_address) DO UPDATE SET
-                ip_address = excluded.ip_address,
+                ip_address = CASE WHEN excluded.ip_address = '0.0.0.0' OR excluded.confidence < assets.confidence
+                        OR excluded.last_seen_at < assets.last_seen_at
+                    THEN assets.ip_address ELSE excluded.ip_address END,
                 hostname = COALESCE(excluded.hostname, assets.hostname),
                 vendor = COALESCE(excluded.vendor, assets.vendor),
-                vlan_id = excluded.vlan_id,
-                discovery_method = excluded.discovery_method,
-                last_seen_at = excluded.last_seen_at",
+                model = COALESCE(excluded.model, assets.model),
+                firmware = COALESCE(excluded.firmware, assets.firmware),
+                os_guess = COALESCE(excluded.os_guess, assets.os_guess),
+                vlan_id = CASE WHEN excluded.confidence < assets.confidence OR excluded.last_seen_at < assets.last_seen_at
+                    THEN assets.vlan_id ELSE excluded.vlan_id END,
+                segment = CASE WHEN excluded.confidence < assets.confidence OR excluded.last_seen_at < assets.last_seen_at
+                    THEN assets.segment ELSE excluded.segment END,
+                discovery_method = CASE WHEN excluded.confidence < assets.confidence OR excluded.last_seen_at < assets.last_seen_at
+                    THEN assets.discovery_method ELSE excluded.discovery_method END,
+                confidence = MAX(assets.confidence, excluded.confidence),
+                first_seen_at = MIN(assets.first_seen_at, excluded.first_seen_at),
+                last_seen_at = MAX(assets.last_seen_at, excluded.last_seen_at)",
             params![
                 asset.mac_address,
                 asset.ip_address,
                 asset.hostname,
                 asset.vendor,
+                asset.model,
+                asset.firmware,
+                asset.os_guess,
                 asset.vlan_id,
+                asset.segment,
                 asset.discovery_method,
+                asset.confidence,
                 asset.first_seen_at,
                 asset.last_seen_at,
             ],
@@ -83,10 +417,724 @@ impl Database {
         Ok(())
     }
 
+    /// Attributes are keyed by (mac, key, value), so a host can hold several roles at once.
+    pub fn sync_attributes(&self, mac: &str, attributes: &[(String, String)], seen_at: DateTime<Utc>) -> Result<()> {
+        let conn = self.pool.get().expect("Failed to get connection from pool");
+        let mut stmt = conn.prepare_cached(
+            "INSERT INTO asset_attributes (mac_address, key, value, first_seen_at, last_seen_at)
+            VALUES (?1, ?2, ?3, ?4, ?4)
+            ON CONFLICT(mac_address, key, value) DO UPDATE SET
+                first_seen_at = MIN(asset_attributes.first_seen_at, excluded.first_seen_at),
+                last_seen_at = MAX(asset_attributes.last_seen_at, excluded.last_seen_at)"
+        )?;
+
+        for (key, value) in attributes {
+            if key == "booted_at"
+                && let Some(known) = Self::same_boot(&conn, mac, value)? {
+                // The newest reading replaces the stored one, so drift never adds up.
+                conn.execute(
+                    "UPDATE asset_attributes SET
+                        value = CASE WHEN ?4 >= last_seen_at THEN ?3 ELSE value END,
+                        last_seen_at = MAX(last_seen_at, ?4)
+                    WHERE mac_address = ?1 AND key = 'booted_at' AND value = ?2",
+                    params![mac, known, value, seen_at],
+                )?;
+                continue;
+            }
+            stmt.execute(params![mac, key, value, seen_at])?;
+        }
+
+        Ok(())
+    }
+
+    /// A different stored `booted_at` value for this MAC within the tolerance of `value`.
+    fn same_boot(conn: &Connection, mac: &str, value: &str) -> Result<Option<String>> {
+        let Ok(booted_at) = NaiveDateTime::parse_from_str(value, BOOT_TIME_FORMAT) else { return Ok(None) };
+        let mut stmt = conn.prepare_cached(
+            "SELECT value FROM asset_attributes WHERE mac_address = ?1 AND key = 'booted_at'"
+        )?;
+        let known = stmt.query_map(params![mac], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>>>()?;
+        if known.iter().any(|k| k == value) {
+            return Ok(None);
+        }
+
+        Ok(known.into_iter().find(|k| {
+            NaiveDateTime::parse_from_str(k, BOOT_TIME_FORMAT)
+                .is_ok_and(|t| (t - booted_at).num_seconds().abs() <= BOOT_TIME_TOLERANCE_SECONDS)
+        }))
+    }
+
+    /// Links facts (e.g. `method=CDP`, `role=gateway`, an observation) to the evidence frame
+    /// they came from. MAC-less discoveries are filed under an empty MAC.
+    pub fn record_evidence(&self, mac: &str, facts: &[String], evidence: &EvidenceRef, captured_at: DateTime<Utc>) -> Result<()> {
+        let conn = self.pool.get().expect("Failed to get connection from pool");
+        let mut stmt = conn.prepare_cached(
+            "INSERT OR IGNORE INTO evidence (mac_address, fact, file, packet_number, captured_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)"
+        )?;
+
+        for fact in facts {
+            stmt.execute(params![mac, fact, evidence.file, evidence.packet_number as i64, captured_at])?;
+        }
+
+        Ok(())
+    }
+
+    /// Forgets the evidence kept in deleted files.
+    pub fn delete_evidence(&self, files: &[String]) -> Result<()> {
+        let conn = self.pool.get().expect("Failed to get connection from pool");
+        let mut stmt = conn.prepare_cached("DELETE FROM evidence WHERE file = ?1")?;
+
+        for file in files {
+            stmt.execute([file])?;
+        }
+
+        Ok(())
+    }
+
+    /// Evidence for an asset, looked up by MAC or by its current IP.
+    pub fn get_evidence(&self, host: &str) -> Result<Vec<EvidenceRow>> {
+        let conn = self.pool.get().expect("Failed to get connection from pool");
+        let mut stmt = conn.prepare(
+            "SELECT fact, file, packet_number, captured_at FROM evidence
+             WHERE mac_address = UPPER(?1)
+                OR mac_address IN (SELECT mac_address FROM assets WHERE ip_address = ?1)
+             ORDER BY captured_at"
+        )?;
+
+        let rows = stmt.query_map([host], |row| {
+            Ok((
+                row.get(0)?,
+                EvidenceRef { file: row.get(1)?, packet_number: row.get::<_, i64>(2)? as u64 },
+                row.get(3)?,
+            ))
+        })?;
+
+        rows.collect()
+    }
+
+    /// Persists a protocol observation and returns any alerts it raised.
+    pub fn record_observation(&self, observation: &Observation, seen_at: DateTime<Utc>) -> Result<Vec<String>> {
+        match observation {
+            Observation::Fhrp(hello) => self.record_fhrp(hello, seen_at),
+            Observation::Routing(hello) => self.record_routing(hello, seen_at),
+            Observation::Bacnet(device) => self.record_bacnet(device, seen_at),
+            Observation::Iec61850(publisher) => self.record_iec61850(publisher, seen_at),
+            Observation::Scada(link) => self.record_scada(link, seen_at),
+            Observation::Ptp(clock) => self.record_ptp(clock, seen_at),
+            Observation::Multicast(membership) => self.record_multicast(membership, seen_at),
+            Observation::MulticastQuerier(querier) => self.record_querier(querier, seen_at),
+            Observation::NtpServer(server) => self.record_ntp_server(server, seen_at),
+            Observation::NtpAssociation(association) => self.record_ntp_association(association, seen_at),
+            Observation::Mqtt(session) => self.record_mqtt(session, seen_at),
+            Observation::WifiDevice(device) => self.record_wifi_device(device, seen_at),
+            Observation::WifiProbe(probe) => self.record_wifi_probe(probe, seen_at),
+            Observation::FlowHost(host) => self.record_flow_host(host, seen_at),
+        }
+    }
+
+    fn record_fhrp(&self, hello: &FhrpHello, seen_at: DateTime<Utc>) -> Result<Vec<String>> {
+        let conn = self.pool.get().expect("Failed to get connection from pool");
+        let mut alerts = Vec::new();
+
+        let known_member: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM fhrp_groups
+             WHERE vlan_id = ?1 AND protocol = ?2 AND group_id = ?3 AND member_ip = ?4)",
+            params![hello.vlan_id, hello.protocol, hello.group_id, hello.member_ip],
+            |row| row.get(0),
+        )?;
+
+        if !known_member {
+            let group_since: Option<DateTime<Utc>> = conn.query_row(
+                "SELECT MIN(first_seen_at) FROM fhrp_groups
+                 WHERE vlan_id = ?1 AND protocol = ?2 AND group_id = ?3",
+                params![hello.vlan_id, hello.protocol, hello.group_id],
+                |row| row.get(0),
+            ).optional()?.flatten();
+
+            if let Some(since) = group_since
+                && seen_at - since > Duration::minutes(FHRP_LEARNING_WINDOW_MINUTES) {
+                alerts.push(format!(
+                    "Unexpected {} speaker {} ({}) joined group {} on VLAN {} with priority {} ({})",
+                    hello.protocol, hello.member_ip, hello.member_mac, hello.group_id,
+                    hello.vlan_id, hello.priority, hello.state
+                ));
+            }
+        }
+
+        conn.execute(
+            "INSERT INTO fhrp_groups (
+                vlan_id, protocol, group_id, member_ip, member_mac, virtual_ip,
+                virtual_mac, priority, state, first_seen_at, last_seen_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?10)
+            ON CONFLICT(vlan_id, protocol, group_id, member_ip) DO UPDATE SET
+                member_mac = excluded.member_mac,
+                virtual_ip = COALESCE(excluded.virtual_ip, fhrp_groups.virtual_ip),
+                virtual_mac = COALESCE(excluded.virtual_mac, fhrp_groups.virtual_mac),
+                priority = excluded.priority,
+                state = excluded.state,
+                first_seen_at = MIN(fhrp_groups.first_seen_at, excluded.first_seen_at),
+                last_seen_at = MAX(fhrp_groups.last_seen_at, excluded.last_seen_at)",
+            params![
+                hello.vlan_id,
+                hello.protocol,
+                hello.group_id,
+                hello.member_ip,
+                hello.member_mac,
+                hello.virtual_ip,
+                hello.virtual_mac,
+                hello.priority,
+                hello.state,
+                seen_at,
+            ],
+        )?;
+
+        Ok(alerts)
+    }
+
+    fn record_routing(&self, hello: &RoutingHello, seen_at: DateTime<Utc>) -> Result<Vec<String>> {
+        let mut conn = self.pool.get().expect("Failed to get connection from pool");
+        let tx = conn.transaction()?;
+        let mut alerts = Vec::new();
+
+        let known: bool = tx.query_row(
+            "SELECT EXISTS(SELECT 1 FROM routers WHERE protocol = ?1 AND router_id = ?2)",
+            params![hello.protocol, hello.router_id],
+            |row| row.get(0),
+        )?;
+
+        if !known {
+            alerts.push(format!(
+                "New {} router {} ({} / {}) on VLAN {}{}",
+                hello.protocol, hello.router_id, hello.speaker_ip, hello.speaker_mac, hello.vlan_id,
+                hello.area.as_ref().map(|a| format!(", area {}", a)).unwrap_or_default()
+            ));
+        }
+
+        tx.execute(
+            "INSERT INTO routers (
+                protocol, router_id, area, vlan_id, ip_address, mac_address, first_seen_at, last_seen_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)
+            ON CONFLICT(protocol, router_id) DO UPDATE SET
+                area = COALESCE(excluded.area, routers.area),
+                vlan_id = excluded.vlan_id,
+                ip_address = excluded.ip_address,
+                mac_address = excluded.mac_address,
+                first_seen_at = MIN(routers.first_seen_at, excluded.first_seen_at),
+                last_seen_at = MAX(routers.last_seen_at, excluded.last_seen_at)",
+            params![
+                hello.protocol,
+                hello.router_id,
+                hello.area,
+                hello.vlan_id,
+                hello.speaker_ip,
+                hello.speaker_mac,
+                seen_at,
+            ],
+        )?;
+
+        for neighbor in &hello.neighbors {
+            tx.execute(
+                "INSERT INTO routing_adjacencies (
+                    protocol, router_id, neighbor_id, vlan_id, first_seen_at, last_seen_at
+                )
+                VALUES (?1, ?2, ?3, ?4, ?5, ?5)
+                ON CONFLICT(protocol, router_id, neighbor_id) DO UPDATE SET
+                    vlan_id = excluded.vlan_id,
+                    first_seen_at = MIN(routing_adjacencies.first_seen_at, excluded.first_seen_at),
+                    last_seen_at = MAX(routing_adjacencies.last_seen_at, excluded.last_seen_at)",
+                params![hello.protocol, hello.router_id, neighbor, hello.vlan_id, seen_at],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(alerts)
+    }
+
+    /// I-Am and I-Have describe the same device; whichever fields a message lacks are kept.
+    fn record_bacnet(&self, device: &BacnetDevice, seen_at: DateTime<Utc>) -> Result<Vec<String>> {
+        let conn = self.pool.get().expect("Failed to get connection from pool");
+
+        conn.execute(
+            "INSERT INTO bacnet_devices (
+                device_instance, vlan_id, ip_address, mac_address, network, network_address,
+                forwarded_by, vendor_id, max_apdu, segmentation, object_name, first_seen_at, last_seen_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?12)
+            ON CONFLICT(device_instance) DO UPDATE SET
+                vlan_id = excluded.vlan_id,
+                ip_address = excluded.ip_address,
+                mac_address = excluded.mac_address,
+                network = excluded.network,
+                network_address = excluded.network_address,
+                forwarded_by = excluded.forwarded_by,
+                vendor_id = COALESCE(excluded.vendor_id, bacnet_devices.vendor_id),
+                max_apdu = COALESCE(excluded.max_apdu, bacnet_devices.max_apdu),
+                segmentation = COALESCE(excluded.segmentation, bacnet_devices.segmentation),
+                object_name = COALESCE(excluded.object_name, bacnet_devices.object_name),
+                first_seen_at = MIN(bacnet_devices.first_seen_at, excluded.first_seen_at),
+                last_seen_at = MAX(bacnet_devices.last_seen_at, excluded.last_seen_at)",
+            params![
+                device.device_instance,
+                device.vlan_id,
+                device.ip_address,
+                device.mac_address,
+                device.network,
+                device.network_address,
+                device.forwarded_by,
+                device.vendor_id,
+                device.max_apdu,
+                device.segmentation,
+                device.object_name,
+                seen_at,
+            ],
+        )?;
+
+        Ok(Vec::new())
+    }
+
+    /// Alerts on publishers not seen before on the VLAN and on confRev changes,
+    /// which mean the IED's dataset configuration was modified.
+    fn record_iec61850(&self, publisher: &Iec61850Publisher, seen_at: DateTime<Utc>) -> Result<Vec<String>> {
+        let conn = self.pool.get().expect("Failed to get connection from pool");
+        let mut alerts = Vec::new();
+
+        let previous_rev: Option<u32> = conn.query_row(
+            "SELECT conf_rev FROM iec61850_publishers
+             WHERE vlan_id = ?1 AND protocol = ?2 AND control_ref = ?3 AND publisher_mac = ?4",
+            params![publisher.vlan_id, publisher.protocol, publisher.control_ref, publisher.publisher_mac],
+            |row| row.get(0),
+        ).optional()?;
+
+        match previous_rev {
+            None => alerts.push(format!(
+                "New {} publisher {} on VLAN {}: {} (APPID 0x{:04X}, confRev {})",
+                publisher.protocol, publisher.publisher_mac, publisher.vlan_id,
+                publisher.control_ref, publisher.appid, publisher.conf_rev
+            )),
+            Some(rev) if rev != publisher.conf_rev => alerts.push(format!(
+                "{} {} from {} on VLAN {} changed confRev {} -> {}",
+                publisher.protocol, publisher.control_ref, publisher.publisher_mac,
+                publisher.vlan_id, rev, publisher.conf_rev
+            )),
+            _ => {}
+        }
+
+        conn.execute(
+            "INSERT INTO iec61850_publishers (
+                vlan_id, protocol, control_ref, publisher_mac, appid, dataset,
+                conf_rev, destination_mac, first_seen_at, last_seen_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?9)
+            ON CONFLICT(vlan_id, protocol, control_ref, publisher_mac) DO UPDATE SET
+                appid = excluded.appid,
+                dataset = COALESCE(excluded.dataset, iec61850_publishers.dataset),
+                conf_rev = excluded.conf_rev,
+                destination_mac = excluded.destination_mac,
+                first_seen_at = MIN(iec61850_publishers.first_seen_at, excluded.first_seen_at),
+                last_seen_at = MAX(iec61850_publishers.last_seen_at, excluded.last_seen_at)",
+            params![
+                publisher.vlan_id,
+                publisher.protocol,
+                publisher.control_ref,
+                publisher.publisher_mac,
+                publisher.appid,
+                publisher.dataset,
+                publisher.conf_rev,
+                publisher.destination_mac,
+                seen_at,
+            ],
+        )?;
+
+        Ok(alerts)
+    }
+
+    fn record_scada(&self, link: &ScadaLink, seen_at: DateTime<Utc>) -> Result<Vec<String>> {
+        let conn = self.pool.get().expect("Failed to get connection from pool");
+
+        conn.execute(
+            "INSERT INTO scada_links (
+                protocol, master_ip, outstation_ip, outstation_address, master_mac,
+                master_address, outstation_mac, vlan_id, first_seen_at, last_seen_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?9)
+            ON CONFLICT(protocol, master_ip, outstation_ip, outstation_address) DO UPDATE SET
+                master_mac = excluded.master_mac,
+                master_address = COALESCE(excluded.master_address, scada_links.master_address),
+                outstation_mac = excluded.outstation_mac,
+                vlan_id = excluded.vlan_id,
+                first_seen_at = MIN(scada_links.first_seen_at, excluded.first_seen_at),
+                last_seen_at = MAX(scada_links.last_seen_at, excluded.last_seen_at)",
+            params![
+                link.protocol,
+                link.master_ip,
+                link.outstation_ip,
+                link.outstation_address,
+                link.master_mac,
+                link.master_address,
+                link.outstation_mac,
+                link.vlan_id,
+                seen_at,
+            ],
+        )?;
+
+        Ok(Vec::new())
+    }
+
+    /// Tracks every announcing clock and the grandmaster each VLAN/domain follows,
+    /// alerting when the latter changes.
+    fn record_ptp(&self, clock: &PtpClock, seen_at: DateTime<Utc>) -> Result<Vec<String>> {
+        let mut conn = self.pool.get().expect("Failed to get connection from pool");
+        let tx = conn.transaction()?;
+        let mut alerts = Vec::new();
+
+        tx.execute(
+            "INSERT INTO ptp_clocks (
+                domain, clock_identity, vlan_id, ip_address, mac_address, role, grandmaster_identity,
+                priority1, priority2, clock_class, steps_removed, first_seen_at, last_seen_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?12)
+            ON CONFLICT(domain, clock_identity) DO UPDATE SET
+                vlan_id = excluded.vlan_id,
+                ip_address = excluded.ip_address,
+                mac_address = excluded.mac_address,
+                role = excluded.role,
+                grandmaster_identity = excluded.grandmaster_identity,
+                priority1 = excluded.priority1,
+                priority2 = excluded.priority2,
+                clock_class = excluded.clock_class,
+                steps_removed = excluded.steps_removed,
+                first_seen_at = MIN(ptp_clocks.first_seen_at, excluded.first_seen_at),
+                last_seen_at = MAX(ptp_clocks.last_seen_at, excluded.last_seen_at)",
+            params![
+                clock.domain,
+                clock.clock_identity,
+                clock.vlan_id,
+                clock.ip_address,
+                clock.mac_address,
+                clock.role,
+                clock.grandmaster_identity,
+                clock.priority1,
+                clock.priority2,
+                clock.clock_class,
+                clock.steps_removed,
+                seen_at,
+            ],
+        )?;
+
+        let previous_gm: Option<String> = tx.query_row(
+            "SELECT grandmaster_identity FROM ptp_domains WHERE vlan_id = ?1 AND domain = ?2",
+            params![clock.vlan_id, clock.domain],
+            |row| row.get(0),
+        ).optional()?;
+
+        if let Some(previous) = &previous_gm
+            && previous != &clock.grandmaster_identity {
+            alerts.push(format!(
+                "PTP grandmaster for domain {} on VLAN {} changed from {} to {} (announced by {}, class {}, priority1 {})",
+                clock.domain, clock.vlan_id, previous, clock.grandmaster_identity,
+                clock.clock_identity, clock.clock_class, clock.priority1
+            ));
+        }
+
+        tx.execute(
+            "INSERT INTO ptp_domains (vlan_id, domain, grandmaster_identity, changed_at, last_seen_at)
+            VALUES (?1, ?2, ?3, ?4, ?4)
+            ON CONFLICT(vlan_id, domain) DO UPDATE SET
+                changed_at = CASE WHEN excluded.grandmaster_identity = ptp_domains.grandmaster_identity
+                    THEN ptp_domains.changed_at ELSE excluded.changed_at END,
+                grandmaster_identity = excluded.grandmaster_identity,
+                last_seen_at = MAX(ptp_domains.last_seen_at, excluded.last_seen_at)",
+            params![clock.vlan_id, clock.domain, clock.grandmaster_identity, seen_at],
+        )?;
+
+        tx.commit()?;
+        Ok(alerts)
+    }
+
+    /// Joins upsert the (listener, group) row; leaves remove it.
+    fn record_multicast(&self, membership: &MulticastMembership, seen_at: DateTime<Utc>) -> Result<Vec<String>> {
+        let conn = self.pool.get().expect("Failed to get connection from pool");
+
+        if membership.joined {
+            conn.execute(
+                "INSERT INTO multicast_memberships (
+                    mac_address, group_address, vlan_id, ip_address, protocol, first_seen_at, last_seen_at
+                )
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)
+                ON CONFLICT(mac_address, group_address) DO UPDATE SET
+                    vlan_id = excluded.vlan_id,
+                    ip_address = excluded.ip_address,
+                    protocol = excluded.protocol,
+                    first_seen_at = MIN(multicast_memberships.first_seen_at, excluded.first_seen_at),
+                    last_seen_at = MAX(multicast_memberships.last_seen_at, excluded.last_seen_at)",
+                params![
+                    membership.member_mac,
+                    membership.group_address,
+                    membership.vlan_id,
+                    membership.member_ip,
+                    membership.protocol,
+                    seen_at,
+                ],
+            )?;
+        } else {
+            conn.execute(
+                "DELETE FROM multicast_memberships WHERE mac_address = ?1 AND group_address = ?2",
+                params![membership.member_mac, membership.group_address],
+            )?;
+        }
+
+        Ok(Vec::new())
+    }
+
+    /// One querier per VLAN and address family; a different sender replaces it and is reported.
+    fn record_querier(&self, querier: &MulticastQuerier, seen_at: DateTime<Utc>) -> Result<Vec<String>> {
+        let mut conn = self.pool.get().expect("Failed to get connection from pool");
+        let tx = conn.transaction()?;
+        let mut alerts = Vec::new();
+        let family = if querier.protocol.starts_with("MLD") { "ipv6" } else { "ipv4" };
+
+        let previous: Option<String> = tx.query_row(
+            "SELECT querier_ip FROM multicast_queriers WHERE vlan_id = ?1 AND family = ?2",
+            params![querier.vlan_id, family],
+            |row| row.get(0),
+        ).optional()?;
+
+        if let Some(previous) = &previous
+            && previous != &querier.querier_ip {
+            alerts.push(format!(
+                "{} querier on VLAN {} changed from {} to {} ({})",
+                querier.protocol, querier.vlan_id, previous, querier.querier_ip, querier.querier_mac
+            ));
+        }
+
+        tx.execute(
+            "INSERT INTO multicast_queriers (
+                vlan_id, family, querier_ip, querier_mac, protocol, first_seen_at, last_seen_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)
+            ON CONFLICT(vlan_id, family) DO UPDATE SET
+                first_seen_at = CASE WHEN excluded.querier_ip = multicast_queriers.querier_ip
+                    THEN multicast_queriers.first_seen_at ELSE excluded.first_seen_at END,
+                querier_ip = excluded.querier_ip,
+                querier_mac = excluded.querier_mac,
+                protocol = excluded.protocol,
+                last_seen_at = MAX(multicast_queriers.last_seen_at, excluded.last_seen_at)",
+            params![querier.vlan_id, family, querier.querier_ip, querier.querier_mac, querier.protocol, seen_at],
+        )?;
+
+        tx.commit()?;
+        Ok(alerts)
+    }
+
+    fn record_ntp_server(&self, server: &NtpServer, seen_at: DateTime<Utc>) -> Result<Vec<String>> {
+        let conn = self.pool.get().expect("Failed to get connection from pool");
+        conn.execute(
+            "INSERT INTO ntp_servers (server_ip, vlan_id, version, stratum, reference_id, first_seen_at, last_seen_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)
+            ON CONFLICT(server_ip) DO UPDATE SET
+                vlan_id = excluded.vlan_id,
+                version = excluded.version,
+                stratum = excluded.stratum,
+                reference_id = excluded.reference_id,
+                first_seen_at = MIN(ntp_servers.first_seen_at, excluded.first_seen_at),
+                last_seen_at = MAX(ntp_servers.last_seen_at, excluded.last_seen_at)",
+            params![server.server_ip, server.vlan_id, server.version, server.stratum, server.reference_id, seen_at],
+        )?;
+        Ok(Vec::new())
+    }
+
+    fn record_ntp_association(&self, association: &NtpAssociation, seen_at: DateTime<Utc>) -> Result<Vec<String>> {
+        let conn = self.pool.get().expect("Failed to get connection from pool");
+        conn.execute(
+            "INSERT INTO ntp_associations (
+                client_ip, server_ip, client_mac, vlan_id, server_public, version, first_seen_at, last_seen_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)
+            ON CONFLICT(client_ip, server_ip) DO UPDATE SET
+                client_mac = excluded.client_mac,
+                vlan_id = excluded.vlan_id,
+                version = excluded.version,
+                first_seen_at = MIN(ntp_associations.first_seen_at, excluded.first_seen_at),
+                last_seen_at = MAX(ntp_associations.last_seen_at, excluded.last_seen_at)",
+            params![
+                association.client_ip,
+                association.server_ip,
+                association.client_mac,
+                association.vlan_id,
+                association.server_public,
+                association.version,
+                seen_at,
+            ],
+        )?;
+        Ok(Vec::new())
+    }
+
+    fn record_mqtt(&self, session: &MqttSession, seen_at: DateTime<Utc>) -> Result<Vec<String>> {
+        let conn = self.pool.get().expect("Failed to get connection from pool");
+        conn.execute(
+            "INSERT INTO mqtt_sessions (
+                client_mac, broker_ip, broker_port, client_ip, vlan_id, client_id, username,
+                protocol_version, first_seen_at, last_seen_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?9)
+            ON CONFLICT(client_mac, broker_ip, broker_port) DO UPDATE SET
+                client_ip = excluded.client_ip,
+                vlan_id = excluded.vlan_id,
+                client_id = COALESCE(excluded.client_id, mqtt_sessions.client_id),
+                username = COALESCE(excluded.username, mqtt_sessions.username),
+                protocol_version = excluded.protocol_version,
+                first_seen_at = MIN(mqtt_sessions.first_seen_at, excluded.first_seen_at),
+                last_seen_at = MAX(mqtt_sessions.last_seen_at, excluded.last_seen_at)",
+            params![
+                session.client_mac,
+                session.broker_ip,
+                session.broker_port,
+                session.client_ip,
+                session.vlan_id,
+                session.client_id,
+                session.username,
+                session.protocol_version,
+                seen_at,
+            ],
+        )?;
+        Ok(Vec::new())
+    }
+
+    fn record_wifi_device(&self, device: &WifiDevice, seen_at: DateTime<Utc>) -> Result<Vec<String>> {
+        let conn = self.pool.get().expect("Failed to get connection from pool");
+        conn.execute(
+            "INSERT INTO wifi_devices (
+                mac_address, role, ssid, bssid, channel, security, signal_dbm, first_seen_at, last_seen_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?8)
+            ON CONFLICT(mac_address) DO UPDATE SET
+                role = excluded.role,
+                ssid = COALESCE(excluded.ssid, wifi_devices.ssid),
+                bssid = excluded.bssid,
+                channel = COALESCE(excluded.channel, wifi_devices.channel),
+                security = COALESCE(excluded.security, wifi_devices.security),
+                signal_dbm = COALESCE(excluded.signal_dbm, wifi_devices.signal_dbm),
+                first_seen_at = MIN(wifi_devices.first_seen_at, excluded.first_seen_at),
+                last_seen_at = MAX(wifi_devices.last_seen_at, excluded.last_seen_at)",
+            params![
+                device.mac_address,
+                device.role,
+                device.ssid,
+                device.bssid,
+                device.channel,
+                device.security,
+                device.signal_dbm,
+                seen_at,
+            ],
+        )?;
+        Ok(Vec::new())
+    }
+
+    fn record_wifi_probe(&self, probe: &WifiProbe, seen_at: DateTime<Utc>) -> Result<Vec<String>> {
+        let conn = self.pool.get().expect("Failed to get connection from pool");
+        conn.execute(
+            "INSERT INTO wifi_probes (client_mac, ssid, signal_dbm, randomized, first_seen_at, last_seen_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?5)
+            ON CONFLICT(client_mac, ssid) DO UPDATE SET
+                signal_dbm = COALESCE(excluded.signal_dbm, wifi_probes.signal_dbm),
+                first_seen_at = MIN(wifi_probes.first_seen_at, excluded.first_seen_at),
+                last_seen_at = MAX(wifi_probes.last_seen_at, excluded.last_seen_at)",
+            params![probe.client_mac, probe.ssid, probe.signal_dbm, probe.randomized, seen_at],
+        )?;
+        Ok(Vec::new())
+    }
+
+    /// Traffic counters accumulate; MAC and VLAN are kept from earlier reports when not exported.
+    fn record_flow_host(&self, host: &FlowHost, seen_at: DateTime<Utc>) -> Result<Vec<String>> {
+        let conn = self.pool.get().expect("Failed to get connection from pool");
+        conn.execute(
+            "INSERT INTO flow_hosts (
+                ip_address, exporter, mac_address, vlan_id, bytes, packets, first_seen_at, last_seen_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)
+            ON CONFLICT(ip_address, exporter) DO UPDATE SET
+                mac_address = COALESCE(excluded.mac_address, flow_hosts.mac_address),
+                vlan_id = COALESCE(excluded.vlan_id, flow_hosts.vlan_id),
+                bytes = flow_hosts.bytes + excluded.bytes,
+                packets = flow_hosts.packets + excluded.packets,
+                first_seen_at = MIN(flow_hosts.first_seen_at, excluded.first_seen_at),
+                last_seen_at = MAX(flow_hosts.last_seen_at, excluded.last_seen_at)",
+            params![
+                host.ip_address,
+                host.exporter,
+                host.mac_address,
+                host.vlan_id,
+                host.bytes as i64,
+                host.packets as i64,
+                seen_at,
+            ],
+        )?;
+        Ok(Vec::new())
+    }
+
+    /// Every client/server pair with the client's hostname and the server's stratum when known.
+    pub fn get_ntp_associations(&self) -> Result<Vec<NtpReportRow>> {
+        let conn = self.pool.get().expect("Failed to get connection from pool");
+        let mut stmt = conn.prepare(
+            "SELECT a.vlan_id, a.client_ip, a.client_mac, a.server_ip, a.server_public, a.version,
+                    assets.hostname, s.stratum
+             FROM ntp_associations a
+             LEFT JOIN assets ON assets.mac_address = a.client_mac
+             LEFT JOIN ntp_servers s ON s.server_ip = a.server_ip
+             ORDER BY a.vlan_id, a.client_ip"
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                NtpAssociation {
+                    vlan_id: row.get(0)?,
+                    client_ip: row.get(1)?,
+                    client_mac: row.get(2)?,
+                    server_ip: row.get(3)?,
+                    server_public: row.get(4)?,
+                    version: row.get(5)?,
+                },
+                row.get(6)?,
+                row.get(7)?,
+            ))
+        })?;
+
+        rows.collect()
+    }
+
+    pub fn get_flow_hosts(&self) -> Result<Vec<FlowReportRow>> {
+        let conn = self.pool.get().expect("Failed to get connection from pool");
+        let mut stmt = conn.prepare(
+            "SELECT ip_address, exporter, mac_address, vlan_id, bytes, packets, first_seen_at, last_seen_at
+             FROM flow_hosts ORDER BY last_seen_at DESC"
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                FlowHost {
+                    ip_address: row.get(0)?,
+                    exporter: row.get(1)?,
+                    mac_address: row.get(2)?,
+                    vlan_id: row.get(3)?,
+                    bytes: row.get::<_, i64>(4)? as u64,
+                    packets: row.get::<_, i64>(5)? as u64,
+                },
+                row.get(6)?,
+                row.get(7)?,
+            ))
+        })?;
+
+        rows.collect()
+    }
+
     pub fn get_all_assets(&self) -> Result<Vec<Asset>> {
         let conn = self.pool.get().expect("Failed to get connection from pool");
         let mut stmt = conn.prepare(
-            "SELECT mac_address, ip_address, hostname, vendor, vlan_id, discovery_method, first_seen_at, last_seen_at 
+            "SELECT mac_address, ip_address, hostname, vendor, model, firmware, os_guess, vlan_id, segment, discovery_method, confidence, first_seen_at, last_seen_at
              FROM assets ORDER BY last_seen_at DESC"
         )?;
         
@@ -96,10 +1144,15 @@ impl Database {
                 ip_address: row.get(1)?,
                 hostname: row.get(2)?,
                 vendor: row.get(3)?,
-                vlan_id: row.get(4)?,
-                discovery_method: row.get(5)?,
-                first_seen_at: row.get(6)?,
-                last_seen_at: row.get(7)?,
+                model: row.get(4)?,
+                firmware: row.get(5)?,
+                os_guess: row.get(6)?,
+                vlan_id: row.get(7)?,
+                segment: row.get(8)?,
+                discovery_method: row.get(9)?,
+                confidence: row.get(10)?,
+                first_seen_at: row.get(11)?,
+                last_seen_at: row.get(12)?,
             })
         })?;
 
@@ -112,7 +1165,27 @@ impl Database {
 
     pub fn reset_database(&self) -> Result<()> {
         let conn = self.pool.get().expect("Failed to get connection from pool");
-        conn.execute("DROP TABLE IF EXISTS assets", [])?;
+        conn.execute_batch("
+            DROP TABLE IF EXISTS assets;
+            DROP TABLE IF EXISTS asset_attributes;
+            DROP TABLE IF EXISTS fhrp_groups;
+            DROP TABLE IF EXISTS routers;
+            DROP TABLE IF EXISTS routing_adjacencies;
+            DROP TABLE IF EXISTS bacnet_devices;
+            DROP TABLE IF EXISTS iec61850_publishers;
+            DROP TABLE IF EXISTS scada_links;
+            DROP TABLE IF EXISTS ptp_clocks;
+            DROP TABLE IF EXISTS ptp_domains;
+            DROP TABLE IF EXISTS multicast_memberships;
+            DROP TABLE IF EXISTS multicast_queriers;
+            DROP TABLE IF EXISTS ntp_servers;
+            DROP TABLE IF EXISTS ntp_associations;
+            DROP TABLE IF EXISTS mqtt_sessions;
+            DROP TABLE IF EXISTS wifi_devices;
+            DROP TABLE IF EXISTS wifi_probes;
+            DROP TABLE IF EXISTS flow_hosts;
+            DROP TABLE IF EXISTS evidence;
+        ")?;
         self.init_db()
     }
 }
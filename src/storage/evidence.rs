@@ -0,0 +1,232 @@
+use crate::domain::models::EvidenceRef;
+use chrono::{DateTime, Utc};
+use pcap::Linktype;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+use tracing::{error, info};
+
+const BLOCK_SECTION_HEADER: u32 = 0x0A0D_0D0A;
+const BLOCK_INTERFACE_DESCRIPTION: u32 = 1;
+const BLOCK_ENHANCED_PACKET: u32 = 6;
+const BYTE_ORDER_MAGIC: u32 = 0x1A2B_3C4D;
+const OPTION_IF_TSRESOL: u16 = 9;
+const SNAPLEN: u32 = 65535;
+/// Frames are buffered and reach the disk on rotation or when `flush` is called.
+const BUFFER_SIZE: usize = 1 << 20;
+
+/// Writes the frames behind discoveries to pcapng files in a directory, starting a new file
+/// once the current one reaches the size or age limit. `prune` keeps the directory under a
+/// total size; the caller drops the database rows pointing into the deleted files.
+pub struct EvidenceWriter {
+    dir: PathBuf,
+    max_bytes: u64,
+    max_age: Duration,
+    max_total_bytes: u64,
+    current: Mutex<Option<EvidenceFile>>,
+}
+
+struct EvidenceFile {
+    name: String,
+    out: BufWriter<File>,
+    /// pcapng interface ID per link type; a file can mix Ethernet, SLL and radiotap frames.
+    interfaces: HashMap<i32, u32>,
+    packets: u64,
+    bytes: u64,
+    opened_at: Instant,
+}
+
+impl EvidenceWriter {
+    pub fn new(dir: PathBuf, max_bytes: u64, max_age: Duration, max_total_bytes: u64) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        Ok(EvidenceWriter { dir, max_bytes, max_age, max_total_bytes, current: Mutex::new(None) })
+    }
+
+    /// Appends one frame and returns where it landed: the file name and its 1-based packet
+    /// number, i.e. Wireshark's `frame.number`.
+    pub fn write(&self, linktype: Linktype, seen_at: DateTime<Utc>, data: &[u8], original_len: u32) -> Option<EvidenceRef> {
+        let mut current = self.current.lock().unwrap_or_else(|e| e.into_inner());
+
+        let expired = current.as_ref()
+            .is_some_and(|f| f.bytes >= self.max_bytes || f.opened_at.elapsed() >= self.max_age);
+        if current.is_none() || expired {
+            if let Some(file) = current.as_mut()
+                && let Err(e) = file.out.flush() {
+                error!("Failed to flush evidence file {}: {}", file.name, e);
+            }
+            *current = match self.open() {
+                Ok(file) => Some(file),
+                Err(e) => {
+                    error!("Failed to open evidence file in {}: {}", self.dir.display(), e);
+                    None
+                }
+            };
+        }
+
+        let file = current.as_mut()?;
+        match file.append(linktype, seen_at, data, original_len) {
+            Ok(()) => Some(EvidenceRef { file: file.name.clone(), packet_number: file.packets }),
+            Err(e) => {
+                error!("Failed to write evidence to {}: {}", file.name, e);
+                *current = None;
+                None
+            }
+        }
+    }
+
+    /// Writes out buffered frames, so the current file can be opened in Wireshark while it is
+    /// still growing. `main` calls this once a second and after the last write.
+    pub fn flush(&self) {
+        let mut current = self.current.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(file) = current.as_mut()
+            && let Err(e) = file.out.flush() {
+            error!("Failed to flush evidence file {}: {}", file.name, e);
+        }
+    }
+
+    /// Deletes the oldest evidence files, never the one being written, until the directory is
+    /// back under its total size. Returns the names of the deleted files.
+    pub fn prune(&self) -> Vec<String> {
+        let current = self.current.lock().unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .map(|f| f.name.clone());
+
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                error!("Failed to list evidence directory {}: {}", self.dir.display(), e);
+                return Vec::new();
+            }
+        };
+        // Oldest first; files opened within the same second are numbered `-1`, `-2`, ...
+        let mut files: Vec<(SystemTime, usize, String, u64)> = entries
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let name = entry.file_name().into_string().ok()?;
+                if !name.starts_with("evidence-") || !name.ends_with(".pcapng") {
+                    return None;
+                }
+                let meta = entry.metadata().ok()?;
+                Some((meta.modified().ok()?, name.len(), name, meta.len()))
+            })
+            .collect();
+        files.sort();
+
+        let mut total: u64 = files.iter().map(|(_, _, _, len)| len).sum();
+        let mut pruned = Vec::new();
+        for (_, _, name, len) in files {
+            if total <= self.max_total_bytes {
+                break;
+            }
+            if current.as_deref() == Some(name.as_str()) {
+                continue;
+            }
+            match fs::remove_file(self.dir.join(&name)) {
+                Ok(()) => {
+                    info!("Deleted evidence file {} to stay under the size limit", name);
+                    total -= len;
+                    pruned.push(name);
+                }
+                Err(e) => error!("Failed to delete evidence file {}: {}", name, e),
+            }
+        }
+        pruned
+    }
+
+    fn open(&self) -> io::Result<EvidenceFile> {
+        let stamp = Utc::now().format("%Y%m%d-%H%M%S");
+        let mut name = format!("evidence-{}.pcapng", stamp);
+        let mut n = 1;
+        while self.dir.join(&name).exists() {
+            name = format!("evidence-{}-{}.pcapng", stamp, n);
+            n += 1;
+        }
+
+        let mut out = BufWriter::with_capacity(BUFFER_SIZE, File::create(self.dir.join(&name))?);
+        let mut shb = Vec::with_capacity(16);
+        shb.extend_from_slice(&BYTE_ORDER_MAGIC.to_le_bytes());
+        shb.extend_from_slice(&1u16.to_le_bytes());
+        shb.extend_from_slice(&0u16.to_le_bytes());
+        shb.extend_from_slice(&(-1i64).to_le_bytes()); // section length not specified
+        let bytes = write_block(&mut out, BLOCK_SECTION_HEADER, &shb)?;
+
+        info!("Writing evidence to {}", self.dir.join(&name).display());
+        Ok(EvidenceFile { name, out, interfaces: HashMap::new(), packets: 0, bytes, opened_at: Instant::now() })
+    }
+}
+
+impl EvidenceFile {
+    fn append(&mut self, linktype: Linktype, seen_at: DateTime<Utc>, data: &[u8], original_len: u32) -> io::Result<()> {
+        // Live captures report DLT_RAW as 12 on some platforms; files always use LINKTYPE_RAW.
+        let linktype = if linktype == Linktype(12) { Linktype::RAW } else { linktype };
+        let interface = match self.interfaces.get(&linktype.0) {
+            Some(id) => *id,
+            None => {
+                // Link type, reserved, snaplen, then if_tsresol = 9 (nanoseconds) and end of options.
+                let mut idb = Vec::with_capacity(20);
+                idb.extend_from_slice(&(linktype.0 as u16).to_le_bytes());
+                idb.extend_from_slice(&0u16.to_le_bytes());
+                idb.extend_from_slice(&SNAPLEN.to_le_bytes());
+                idb.extend_from_slice(&OPTION_IF_TSRESOL.to_le_bytes());
+                idb.extend_from_slice(&1u16.to_le_bytes());
+                idb.extend_from_slice(&[9, 0, 0, 0]);
+                idb.extend_from_slice(&[0, 0, 0, 0]);
+                self.bytes += write_block(&mut self.out, BLOCK_INTERFACE_DESCRIPTION, &idb)?;
+
+                let id = self.interfaces.len() as u32;
+                self.interfaces.insert(linktype.0, id);
+                id
+            }
+        };
+
+        let ts = seen_at.timestamp_nanos_opt().unwrap_or_default() as u64;
+        let mut epb = Vec::with_capacity(20 + data.len() + 3);
+        epb.extend_from_slice(&interface.to_le_bytes());
+        epb.extend_from_slice(&((ts >> 32) as u32).to_le_bytes());
+        epb.extend_from_slice(&(ts as u32).to_le_bytes());
+        epb.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        epb.extend_from_slice(&original_len.max(data.len() as u32).to_le_bytes());
+        epb.extend_from_slice(data);
+        epb.resize(epb.len().div_ceil(4) * 4, 0);
+        self.bytes += write_block(&mut self.out, BLOCK_ENHANCED_PACKET, &epb)?;
+        self.packets += 1;
+        Ok(())
+    }
+}
+
+/// Block type, total length, body (already padded to 4 bytes), total length again.
+fn write_block(out: &mut impl Write, block_type: u32, body: &[u8]) -> io::Result<u64> {
+    let total = (body.len() + 12) as u32;
+    out.write_all(&block_type.to_le_bytes())?;
+    out.write_all(&total.to_le_bytes())?;
+    out.write_all(body)?;
+    out.write_all(&total.to_le_bytes())?;
+    Ok(total as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prune_keeps_current_file() {
+        let dir = std::env::temp_dir().join(format!("field_watcher-evidence-{}", std::process::id()));
+        // Every frame starts a new file; nothing but the current file fits the total.
+        let writer = EvidenceWriter::new(dir.clone(), 1, Duration::from_secs(3600), 0).unwrap();
+        let frame = [0u8; 60];
+        let written: Vec<EvidenceRef> = (0..3)
+            .map(|_| writer.write(Linktype::ETHERNET, Utc::now(), &frame, 60).unwrap())
+            .collect();
+        assert!(written.iter().all(|r| r.packet_number == 1));
+
+        let pruned = writer.prune();
+        assert_eq!(pruned, vec![written[0].file.clone(), written[1].file.clone()]);
+        let left: Vec<_> = fs::read_dir(&dir).unwrap().map(|e| e.unwrap().file_name()).collect();
+        assert_eq!(left, vec![std::ffi::OsString::from(&written[2].file)]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}